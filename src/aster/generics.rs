@@ -1,4 +1,5 @@
-use {Generics, Ident, LifetimeDef, TyParam, WhereClause, WherePredicate};
+use {ConstParam, GenericParam, Generics, Ident, LifetimeDef, TyParam, WhereClause, WherePredicate,
+     EMPTY_SPAN};
 use aster::invoke::{Identity, Invoke};
 use aster::lifetime::{IntoLifetime, LifetimeDefBuilder, IntoLifetimeDef};
 use aster::path::IntoPath;
@@ -8,7 +9,7 @@ use aster::where_predicate::WherePredicateBuilder;
 pub struct GenericsBuilder<F = Identity> {
     callback: F,
     lifetimes: Vec<LifetimeDef>,
-    ty_params: Vec<TyParam>,
+    params: Vec<GenericParam>,
     predicates: Vec<WherePredicate>,
 }
 
@@ -29,7 +30,7 @@ impl<F> GenericsBuilder<F>
         GenericsBuilder {
             callback: callback,
             lifetimes: Vec::new(),
-            ty_params: Vec::new(),
+            params: Vec::new(),
             predicates: Vec::new(),
         }
     }
@@ -38,14 +39,14 @@ impl<F> GenericsBuilder<F>
         GenericsBuilder {
             callback: callback,
             lifetimes: generics.lifetimes,
-            ty_params: generics.ty_params,
+            params: generics.params,
             predicates: generics.where_clause.predicates,
         }
     }
 
     pub fn with(self, generics: Generics) -> Self {
         self.with_lifetimes(generics.lifetimes.into_iter())
-            .with_ty_params(generics.ty_params.into_iter())
+            .with_params(generics.params.into_iter())
             .with_predicates(generics.where_clause.predicates.into_iter())
     }
 
@@ -85,10 +86,17 @@ impl<F> GenericsBuilder<F>
         LifetimeDefBuilder::with_callback(name, self)
     }
 
+    pub fn with_params<I>(mut self, iter: I) -> Self
+        where I: IntoIterator<Item = GenericParam>
+    {
+        self.params.extend(iter);
+        self
+    }
+
     pub fn with_ty_params<I>(mut self, iter: I) -> Self
         where I: IntoIterator<Item = TyParam>
     {
-        self.ty_params.extend(iter);
+        self.params.extend(iter.into_iter().map(GenericParam::Type));
         self
     }
 
@@ -103,7 +111,7 @@ impl<F> GenericsBuilder<F>
     }
 
     pub fn with_ty_param(mut self, ty_param: TyParam) -> Self {
-        self.ty_params.push(ty_param);
+        self.params.push(GenericParam::Type(ty_param));
         self
     }
 
@@ -119,6 +127,18 @@ impl<F> GenericsBuilder<F>
         TyParamBuilder::with_callback(id, self)
     }
 
+    pub fn with_const_params<I>(mut self, iter: I) -> Self
+        where I: IntoIterator<Item = ConstParam>
+    {
+        self.params.extend(iter.into_iter().map(GenericParam::Const));
+        self
+    }
+
+    pub fn with_const_param(mut self, const_param: ConstParam) -> Self {
+        self.params.push(GenericParam::Const(const_param));
+        self
+    }
+
     pub fn with_predicates<I>(mut self, iter: I) -> Self
         where I: IntoIterator<Item = WherePredicate>
     {
@@ -144,10 +164,12 @@ impl<F> GenericsBuilder<F>
             lifetime_def.bounds.push(lifetime.clone());
         }
 
-        for ty_param in &mut self.ty_params {
-            *ty_param = TyParamBuilder::from_ty_param(ty_param.clone())
-                .lifetime_bound(lifetime.clone())
-                .build();
+        for param in &mut self.params {
+            if let GenericParam::Type(ref mut ty_param) = *param {
+                *ty_param = TyParamBuilder::from_ty_param(ty_param.clone())
+                    .lifetime_bound(lifetime.clone())
+                    .build();
+            }
         }
 
         self
@@ -158,11 +180,13 @@ impl<F> GenericsBuilder<F>
     {
         let path = path.into_path();
 
-        for ty_param in &mut self.ty_params {
-            *ty_param = TyParamBuilder::from_ty_param(ty_param.clone())
-                .trait_bound(path.clone())
-                .build()
-                .build();
+        for param in &mut self.params {
+            if let GenericParam::Type(ref mut ty_param) = *param {
+                *ty_param = TyParamBuilder::from_ty_param(ty_param.clone())
+                    .trait_bound(path.clone())
+                    .build()
+                    .build();
+            }
         }
 
         self
@@ -182,8 +206,10 @@ impl<F> GenericsBuilder<F>
     }
 
     pub fn strip_ty_params(mut self) -> Self {
-        for ty_param in &mut self.ty_params {
-            ty_param.bounds = vec![];
+        for param in &mut self.params {
+            if let GenericParam::Type(ref mut ty_param) = *param {
+                ty_param.bounds = vec![];
+            }
         }
         self
     }
@@ -196,8 +222,12 @@ impl<F> GenericsBuilder<F>
     pub fn build(self) -> F::Result {
         self.callback.invoke(Generics {
             lifetimes: self.lifetimes,
-            ty_params: self.ty_params,
-            where_clause: WhereClause { predicates: self.predicates },
+            params: self.params,
+            where_clause: WhereClause {
+                predicates: self.predicates,
+                where_span: None,
+            },
+            span: EMPTY_SPAN,
         })
     }
 }
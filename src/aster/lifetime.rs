@@ -1,4 +1,4 @@
-use {Ident, Lifetime, LifetimeDef};
+use {Ident, Lifetime, LifetimeDef, EMPTY_SPAN};
 use aster::invoke::{Invoke, Identity};
 
 // ////////////////////////////////////////////////////////////////////////////
@@ -37,6 +37,7 @@ impl IntoLifetimeDef for Lifetime {
             attrs: vec![],
             lifetime: self,
             bounds: vec![],
+            span: EMPTY_SPAN,
         }
     }
 }
@@ -98,6 +99,7 @@ impl<F> LifetimeDefBuilder<F>
             attrs: vec![],
             lifetime: self.lifetime,
             bounds: self.bounds,
+            span: EMPTY_SPAN,
         })
     }
 }
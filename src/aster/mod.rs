@@ -10,10 +10,16 @@ pub mod ty;
 pub mod ty_param;
 pub mod where_predicate;
 
+/// Build an identifier. Identifiers without a location are given `EMPTY_SPAN`
+/// to mark them as synthetic.
 pub fn id<I>(id: I) -> Ident
     where I: Into<Ident>
 {
-    id.into()
+    let mut id = id.into();
+    if id.span.is_none() {
+        id.span = Some(EMPTY_SPAN);
+    }
+    id
 }
 
 pub fn from_generics(generics: Generics) -> generics::GenericsBuilder {
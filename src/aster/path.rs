@@ -1,5 +1,5 @@
-use {AngleBracketedParameterData, Generics, Ident, Lifetime, ParenthesizedParameterData, Path,
-     PathParameters, PathSegment, Ty, TypeBinding};
+use {AngleBracketedParameterData, GenericArg, GenericParam, Generics, Ident, Lifetime,
+     ParenthesizedParameterData, Path, PathParameters, PathSegment, Ty, TypeBinding, EMPTY_SPAN};
 use aster::ident::ToIdent;
 use aster::invoke::{Invoke, Identity};
 use aster::lifetime::IntoLifetime;
@@ -141,6 +141,7 @@ impl<F> PathSegmentsBuilder<F>
         self.callback.invoke(Path {
             global: self.global,
             segments: self.segments,
+            span: EMPTY_SPAN,
         })
     }
 }
@@ -185,9 +186,14 @@ impl<F> PathSegmentBuilder<F>
             .iter()
             .map(|lifetime_def| lifetime_def.lifetime.clone());
 
-        let tys = generics.ty_params
+        // Const parameters are passed by name, which parses as a type.
+        let tys = generics.params
             .iter()
-            .map(|ty_param| TyBuilder::new().id(ty_param.ident.clone()));
+            .map(|param| match *param {
+                GenericParam::Type(ref ty_param) => &ty_param.ident,
+                GenericParam::Const(ref const_param) => &const_param.ident,
+            })
+            .map(|ident| TyBuilder::new().id(ident.clone()));
 
         self.with_lifetimes(lifetimes)
             .with_tys(tys)
@@ -265,14 +271,16 @@ impl<F> PathSegmentBuilder<F>
         self.callback.invoke(PathSegment {
             ident: self.id,
             parameters: parameters,
+            span: EMPTY_SPAN,
         })
     }
 
     pub fn build(self) -> F::Result {
         let data = AngleBracketedParameterData {
             lifetimes: self.lifetimes,
-            types: self.tys,
+            args: self.tys.into_iter().map(GenericArg::Type).collect(),
             bindings: self.bindings,
+            span: EMPTY_SPAN,
         };
 
         let parameters = PathParameters::AngleBracketed(data);
@@ -280,6 +288,7 @@ impl<F> PathSegmentBuilder<F>
         self.callback.invoke(PathSegment {
             ident: self.id,
             parameters: parameters,
+            span: EMPTY_SPAN,
         })
     }
 }
@@ -1,4 +1,4 @@
-use {Path, PathSegment, QSelf, Ty};
+use {Path, PathSegment, QSelf, Ty, EMPTY_SPAN};
 use aster::ident::ToIdent;
 use aster::invoke::{Invoke, Identity};
 use aster::path::{PathBuilder, PathSegmentBuilder};
@@ -78,6 +78,7 @@ impl<F> QPathTyBuilder<F>
         let path = Path {
             global: false,
             segments: vec![],
+            span: EMPTY_SPAN,
         };
         self.as_().build(path).id(id)
     }
@@ -88,6 +89,7 @@ impl<F> QPathTyBuilder<F>
         let path = Path {
             global: false,
             segments: vec![],
+            span: EMPTY_SPAN,
         };
         self.as_().build(path).segment(id)
     }
@@ -104,6 +106,7 @@ impl<F> Invoke<Path> for QPathTyBuilder<F>
             qself: QSelf {
                 ty: Box::new(self.ty),
                 position: path.segments.len(),
+                span: EMPTY_SPAN,
             },
             path: path,
         }
@@ -1,4 +1,5 @@
-use {Generics, Lifetime, MutTy, Mutability, Path, QSelf, Ty, TyParamBound};
+use {Generics, Lifetime, MutTy, Mutability, Path, QSelf, Span, Ty, TyKind, TyParamBound,
+     EMPTY_SPAN};
 use aster::ident::ToIdent;
 use aster::invoke::{Invoke, Identity};
 use aster::lifetime::IntoLifetime;
@@ -10,6 +11,7 @@ use aster::ty_param::TyParamBoundBuilder;
 
 pub struct TyBuilder<F = Identity> {
     callback: F,
+    span: Span,
 }
 
 impl TyBuilder {
@@ -22,13 +24,27 @@ impl<F> TyBuilder<F>
     where F: Invoke<Ty>
 {
     pub fn with_callback(callback: F) -> Self {
-        TyBuilder { callback: callback }
+        TyBuilder {
+            callback: callback,
+            span: EMPTY_SPAN,
+        }
+    }
+
+    /// Set the span which will be attached to the built type.
+    pub fn span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
     }
 
     pub fn build(self, ty: Ty) -> F::Result {
         self.callback.invoke(ty)
     }
 
+    pub fn build_ty_kind(self, ty: TyKind) -> F::Result {
+        let span = self.span;
+        self.build(Ty::new(ty, span))
+    }
+
     pub fn id<I>(self, id: I) -> F::Result
         where I: ToIdent
     {
@@ -36,11 +52,11 @@ impl<F> TyBuilder<F>
     }
 
     pub fn build_path(self, path: Path) -> F::Result {
-        self.build(Ty::Path(None, path))
+        self.build_ty_kind(TyKind::Path(None, path))
     }
 
     pub fn build_qpath(self, qself: QSelf, path: Path) -> F::Result {
-        self.build(Ty::Path(Some(qself), path))
+        self.build_ty_kind(TyKind::Path(Some(qself), path))
     }
 
     pub fn path(self) -> PathBuilder<TyPathBuilder<F>> {
@@ -115,7 +131,7 @@ impl<F> TyBuilder<F>
     }
 
     pub fn build_slice(self, ty: Ty) -> F::Result {
-        self.build(Ty::Slice(Box::new(ty)))
+        self.build_ty_kind(TyKind::Slice(Box::new(ty)))
     }
 
     pub fn slice(self) -> TyBuilder<TySliceBuilder<F>> {
@@ -131,11 +147,11 @@ impl<F> TyBuilder<F>
     }
 
     pub fn never(self) -> F::Result {
-        self.build(Ty::Never)
+        self.build_ty_kind(TyKind::Never)
     }
 
     pub fn infer(self) -> F::Result {
-        self.build(Ty::Infer)
+        self.build_ty_kind(TyKind::Infer)
     }
 
     pub fn option(self) -> TyBuilder<TyOptionBuilder<F>> {
@@ -236,7 +252,7 @@ impl<F> TyRefBuilder<F>
             ty: ty,
             mutability: self.mutability,
         };
-        self.builder.build(Ty::Rptr(self.lifetime, Box::new(ty)))
+        self.builder.build_ty_kind(TyKind::Rptr(self.lifetime, Box::new(ty)))
     }
 
     pub fn ty(self) -> TyBuilder<Self> {
@@ -434,7 +450,7 @@ impl<F> TyImplTraitTyBuilder<F>
 
     pub fn build(self) -> F::Result {
         let bounds = self.bounds;
-        self.builder.build(Ty::ImplTrait(bounds))
+        self.builder.build_ty_kind(TyKind::ImplTrait(bounds))
     }
 }
 
@@ -475,7 +491,7 @@ impl<F> TyTupleBuilder<F>
     }
 
     pub fn build(self) -> F::Result {
-        self.builder.build(Ty::Tup(self.tys))
+        self.builder.build_ty_kind(TyKind::Tup(self.tys))
     }
 }
 
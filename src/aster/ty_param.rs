@@ -1,4 +1,5 @@
-use {Ident, LifetimeDef, Path, PolyTraitRef, TraitBoundModifier, Ty, TyParam, TyParamBound};
+use {Ident, LifetimeDef, Path, PolyTraitRef, TraitBoundModifier, Ty, TyParam, TyParamBound,
+     EMPTY_SPAN};
 use aster::invoke::{Invoke, Identity};
 use aster::lifetime::{IntoLifetime, IntoLifetimeDef, LifetimeDefBuilder};
 use aster::path::{IntoPath, PathBuilder};
@@ -91,6 +92,7 @@ impl<F> TyParamBuilder<F>
             ident: self.id,
             bounds: self.bounds,
             default: self.default,
+            span: EMPTY_SPAN,
         })
     }
 }
@@ -1,5 +1,5 @@
 use {Ident, Lifetime, LifetimeDef, Ty, TyParamBound, WhereBoundPredicate, WherePredicate,
-     WhereRegionPredicate};
+     WhereRegionPredicate, EMPTY_SPAN};
 use aster::invoke::{Invoke, Identity};
 use aster::lifetime::{IntoLifetime, IntoLifetimeDef, LifetimeDefBuilder};
 use aster::path::IntoPath;
@@ -204,6 +204,7 @@ impl<F> WhereBoundPredicateTyBoundsBuilder<F>
             bound_lifetimes: self.bound_lifetimes,
             bounded_ty: self.ty,
             bounds: self.bounds,
+            span: EMPTY_SPAN,
         };
 
         self.callback.invoke(WherePredicate::BoundPredicate(predicate))
@@ -252,6 +253,7 @@ impl<F> WhereRegionPredicateBuilder<F>
         let predicate = WhereRegionPredicate {
             lifetime: self.lifetime,
             bounds: self.bounds,
+            span: EMPTY_SPAN,
         };
 
         self.callback.invoke(WherePredicate::RegionPredicate(predicate))
@@ -30,7 +30,40 @@ pub enum AttrStyle {
 ///
 /// E.g. `#[test]`, `#[derive(..)]` or `#[feature = "foo"]`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub enum MetaItem {
+pub struct MetaItem {
+    pub node: MetaItemKind,
+    /// The span from the name to the end of the meta item, not including the
+    /// surrounding `#[` and `]`
+    pub span: Span,
+}
+
+impl MetaItem {
+    pub fn new(node: MetaItemKind, span: Span) -> MetaItem {
+        MetaItem {
+            node: node,
+            span: span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.node.name()
+    }
+}
+
+impl From<Spanned<MetaItemKind>> for MetaItem {
+    fn from(spanned: Spanned<MetaItemKind>) -> MetaItem {
+        MetaItem::new(spanned.node, spanned.span)
+    }
+}
+
+impl From<MetaItemKind> for MetaItem {
+    fn from(node: MetaItemKind) -> MetaItem {
+        MetaItem::new(node, EMPTY_SPAN)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum MetaItemKind {
     /// Word meta item.
     ///
     /// E.g. `test` as in `#[test]`
@@ -45,21 +78,52 @@ pub enum MetaItem {
     NameValue(Ident, Lit),
 }
 
-impl MetaItem {
+impl MetaItemKind {
     pub fn name(&self) -> &str {
         match *self {
-            MetaItem::Word(ref name) |
-            MetaItem::List(ref name, _) |
-            MetaItem::NameValue(ref name, _) => name.as_ref(),
+            MetaItemKind::Word(ref name) |
+            MetaItemKind::List(ref name, _) |
+            MetaItemKind::NameValue(ref name, _) => name.as_ref(),
         }
     }
+
+    pub fn span(self, span: Span) -> MetaItem {
+        MetaItem::new(self, span)
+    }
 }
 
 /// Possible values inside of compile-time attribute lists.
 ///
 /// E.g. the '..' in `#[name(..)]`.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub enum NestedMetaItem {
+pub struct NestedMetaItem {
+    pub node: NestedMetaItemKind,
+    pub span: Span,
+}
+
+impl NestedMetaItem {
+    pub fn new(node: NestedMetaItemKind, span: Span) -> NestedMetaItem {
+        NestedMetaItem {
+            node: node,
+            span: span,
+        }
+    }
+}
+
+impl From<Spanned<NestedMetaItemKind>> for NestedMetaItem {
+    fn from(spanned: Spanned<NestedMetaItemKind>) -> NestedMetaItem {
+        NestedMetaItem::new(spanned.node, spanned.span)
+    }
+}
+
+impl From<NestedMetaItemKind> for NestedMetaItem {
+    fn from(node: NestedMetaItemKind) -> NestedMetaItem {
+        NestedMetaItem::new(node, EMPTY_SPAN)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum NestedMetaItemKind {
     /// A full MetaItem, for recursive meta items.
     MetaItem(MetaItem),
     /// A literal.
@@ -68,6 +132,12 @@ pub enum NestedMetaItem {
     Literal(Lit),
 }
 
+impl NestedMetaItemKind {
+    pub fn span(self, span: Span) -> NestedMetaItem {
+        NestedMetaItem::new(self, span)
+    }
+}
+
 pub trait FilterAttrs<'a> {
     type Ret: Iterator<Item = &'a Attribute>;
 
@@ -119,20 +189,16 @@ pub mod parsing {
         )
         |
         do_parse!(
-            punct!("//!") >>
-            content: spanned!(take_until!("\n")) >>
+            content: spanned!(preceded!(
+                punct!("//!"),
+                take_until!("\n")
+            )) >>
             (Attribute {
                 style: AttrStyle::Inner,
-                value: MetaItem::NameValue(
-                    "doc".into(),
-                    Lit {
-                        span: Span {
-                            lo: content.span.lo - 3, // include the '//!'
-                            hi: content.span.hi,
-                        },
-                        .. format!("//!{}", content.node).into()
-                    }
-                ),
+                value: doc_meta_item(Lit {
+                    span: content.span,
+                    .. format!("//!{}", content.node).into()
+                }),
                 is_sugared_doc: true,
                 span: DUMMY_SPAN,
             })
@@ -144,10 +210,7 @@ pub mod parsing {
             com: spanned!(block_comment) >>
             (Attribute {
                 style: AttrStyle::Inner,
-                value: MetaItem::NameValue(
-                    "doc".into(),
-                    com.into(),
-                ),
+                value: doc_meta_item(com.into()),
                 is_sugared_doc: true,
                 span: DUMMY_SPAN,
             })
@@ -169,21 +232,16 @@ pub mod parsing {
         )
         |
         do_parse!(
-            punct!("///") >>
-            not!(tag!("/")) >>
-            content: spanned!(take_until!("\n")) >>
+            content: spanned!(preceded!(
+                tuple!(punct!("///"), not!(tag!("/"))),
+                take_until!("\n")
+            )) >>
             (Attribute {
                 style: AttrStyle::Outer,
-                value: MetaItem::NameValue(
-                    "doc".into(),
-                    Lit {
-                        span: Span {
-                            lo: content.span.lo - 3, // Include the '///'
-                            hi: content.span.hi,
-                        },
-                        .. format!("///{}", content.node).into()
-                    },
-                ),
+                value: doc_meta_item(Lit {
+                    span: content.span,
+                    .. format!("///{}", content.node).into()
+                }),
                 is_sugared_doc: true,
                 span: DUMMY_SPAN,
             })
@@ -195,40 +253,43 @@ pub mod parsing {
             com: spanned!(block_comment) >>
             (Attribute {
                 style: AttrStyle::Outer,
-                value: MetaItem::NameValue(
-                    "doc".into(),
-                    com.into(),
-                ),
+                value: doc_meta_item(com.into()),
                 is_sugared_doc: true,
                 span: DUMMY_SPAN,
             })
         )
     )));
 
-    named!(meta_item -> MetaItem, alt!(
+    /// The meta item of a sugared doc comment, which spans the whole comment.
+    fn doc_meta_item(lit: Lit) -> MetaItem {
+        let span = lit.span;
+        MetaItemKind::NameValue("doc".into(), lit).span(span)
+    }
+
+    named!(meta_item -> MetaItem, map!(spanned!(alt!(
         do_parse!(
             id: ident >>
             punct!("(") >>
             inner: terminated_list!(punct!(","), nested_meta_item) >>
             punct!(")") >>
-            (MetaItem::List(id, inner))
+            (MetaItemKind::List(id, inner))
         )
         |
         do_parse!(
             name: ident >>
             punct!("=") >>
             value: lit >>
-            (MetaItem::NameValue(name, value))
+            (MetaItemKind::NameValue(name, value))
         )
         |
-        map!(ident, MetaItem::Word)
-    ));
+        map!(ident, MetaItemKind::Word)
+    )), <MetaItem as From<Spanned<MetaItemKind>>>::from));
 
-    named!(nested_meta_item -> NestedMetaItem, alt!(
-        meta_item => { NestedMetaItem::MetaItem }
+    named!(nested_meta_item -> NestedMetaItem, map!(spanned!(alt!(
+        meta_item => { NestedMetaItemKind::MetaItem }
         |
-        lit => { NestedMetaItem::Literal }
-    ));
+        lit => { NestedMetaItemKind::Literal }
+    )), <NestedMetaItem as From<Spanned<NestedMetaItemKind>>>::from));
 }
 
 #[cfg(feature = "printing")]
@@ -240,8 +301,10 @@ mod printing {
     impl ToTokens for Attribute {
         fn to_tokens(&self, tokens: &mut Tokens) {
             if let Attribute { style,
-                               value: MetaItem::NameValue(ref name, Lit {
-                                   node: LitKind::Str(ref value, StrStyle::Cooked), .. }),
+                               value: MetaItem {
+                                   node: MetaItemKind::NameValue(ref name, Lit {
+                                       node: LitKind::Str(ref value, StrStyle::Cooked), .. }),
+                                   .. },
                                is_sugared_doc: true, .. } = *self {
                 if name == "doc" {
                     match style {
@@ -278,17 +341,17 @@ mod printing {
 
     impl ToTokens for MetaItem {
         fn to_tokens(&self, tokens: &mut Tokens) {
-            match *self {
-                MetaItem::Word(ref ident) => {
+            match self.node {
+                MetaItemKind::Word(ref ident) => {
                     ident.to_tokens(tokens);
                 }
-                MetaItem::List(ref ident, ref inner) => {
+                MetaItemKind::List(ref ident, ref inner) => {
                     ident.to_tokens(tokens);
                     tokens.append("(");
                     tokens.append_separated(inner, ",");
                     tokens.append(")");
                 }
-                MetaItem::NameValue(ref name, ref value) => {
+                MetaItemKind::NameValue(ref name, ref value) => {
                     name.to_tokens(tokens);
                     tokens.append("=");
                     value.to_tokens(tokens);
@@ -299,11 +362,11 @@ mod printing {
 
     impl ToTokens for NestedMetaItem {
         fn to_tokens(&self, tokens: &mut Tokens) {
-            match *self {
-                NestedMetaItem::MetaItem(ref nested) => {
+            match self.node {
+                NestedMetaItemKind::MetaItem(ref nested) => {
                     nested.to_tokens(tokens);
                 }
-                NestedMetaItem::Literal(ref lit) => {
+                NestedMetaItemKind::Literal(ref lit) => {
                     lit.to_tokens(tokens);
                 }
             }
@@ -22,6 +22,8 @@ pub enum ConstExpr {
     Index(Box<ConstExpr>, Box<ConstExpr>),
     /// No-op: used solely so we can pretty-print faithfully
     Paren(Box<ConstExpr>),
+    /// A const generic argument in braces (`{ N + 1 }`)
+    Block(Box<ConstExpr>),
     /// If compiling with full support for expression syntax, any expression is
     /// allowed
     Other(Other),
@@ -39,7 +41,11 @@ pub type Other = Expr;
 #[cfg(feature = "parsing")]
 pub mod parsing {
     use super::*;
-    use {BinOp, Ty};
+    use {BinOp, Ty, UnOp};
+    #[cfg(feature = "full")]
+    use {ExprKind, Spanned, Unsafety};
+    #[cfg(feature = "full")]
+    use expr::parsing::block;
     use lit::parsing::lit;
     use op::parsing::{binop, unop};
     use ty::parsing::{path, ty};
@@ -103,6 +109,43 @@ pub mod parsing {
         (ConstExpr::Paren(Box::new(e)))
     ));
 
+    named!(expr_block -> ConstExpr, do_parse!(
+        punct!("{") >>
+        e: const_expr >>
+        punct!("}") >>
+        (ConstExpr::Block(Box::new(e)))
+    ));
+
+    // A const generic argument which can not be mistaken for a type: a literal,
+    // a negated literal or an expression in braces.
+    #[cfg(not(feature = "full"))]
+    named!(pub const_arg -> ConstExpr, alt!(
+        expr_lit
+        |
+        expr_neg_lit
+        |
+        expr_block
+    ));
+
+    #[cfg(feature = "full")]
+    named!(pub const_arg -> ConstExpr, alt!(
+        expr_lit
+        |
+        expr_neg_lit
+        |
+        expr_block
+        |
+        map!(spanned!(block), |Spanned { node, span }| {
+            ConstExpr::Other(ExprKind::Block(Unsafety::Normal, node).span(span))
+        })
+    ));
+
+    named!(expr_neg_lit -> ConstExpr, do_parse!(
+        punct!("-") >>
+        l: lit >>
+        (ConstExpr::Unary(UnOp::Neg, Box::new(ConstExpr::Lit(l))))
+    ));
+
     named!(and_cast -> Ty, do_parse!(
         keyword!("as") >>
         ty: ty >>
@@ -151,6 +194,11 @@ mod printing {
                     expr.to_tokens(tokens);
                     tokens.append(")");
                 }
+                ConstExpr::Block(ref expr) => {
+                    tokens.append("{");
+                    expr.to_tokens(tokens);
+                    tokens.append("}");
+                }
                 ConstExpr::Other(ref other) => {
                     other.to_tokens(tokens);
                 }
@@ -7,6 +7,11 @@ pub struct Variant {
     pub data: VariantData,
     /// Explicit discriminant, e.g. `Foo = 1`
     pub discriminant: Option<ConstExpr>,
+    /// The span of the explicit discriminant expression, not including the `=`
+    pub discriminant_span: Option<Span>,
+    /// The span from the first attribute or the identifier to the end of the
+    /// variant, including its discriminant
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -40,10 +45,42 @@ pub struct Field {
     pub vis: Visibility,
     pub attrs: Vec<Attribute>,
     pub ty: Ty,
+    /// The span of the `:` between the name and the type, or `None` for tuple
+    /// fields
+    pub colon_span: Option<Span>,
+    /// The span from the first attribute or the visibility to the end of the
+    /// type
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub enum Visibility {
+pub struct Visibility {
+    pub node: VisibilityKind,
+    /// The span of the `pub` qualifier, or `None` if the visibility is
+    /// inherited or was not produced by the parser.
+    pub span: Option<Span>,
+}
+
+impl From<Spanned<VisibilityKind>> for Visibility {
+    fn from(spanned: Spanned<VisibilityKind>) -> Visibility {
+        Visibility {
+            node: spanned.node,
+            span: Some(spanned.span),
+        }
+    }
+}
+
+impl From<VisibilityKind> for Visibility {
+    fn from(node: VisibilityKind) -> Visibility {
+        Visibility {
+            node: node,
+            span: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum VisibilityKind {
     Public,
     Crate,
     Restricted(Box<Path>),
@@ -89,7 +126,7 @@ pub mod parsing {
         (wh, variants)
     ));
 
-    named!(variant -> Variant, do_parse!(
+    named!(variant -> Variant, add_span!(do_parse!(
         attrs: many0!(outer_attr) >>
         id: ident >>
         data: alt!(
@@ -99,14 +136,16 @@ pub mod parsing {
             |
             epsilon!() => { |_| VariantData::Unit }
         ) >>
-        disr: option!(preceded!(punct!("="), const_expr)) >>
+        disr: option!(preceded!(punct!("="), spanned!(const_expr))) >>
         (Variant {
             ident: id,
             attrs: attrs,
             data: data,
-            discriminant: disr,
+            discriminant_span: disr.as_ref().map(|disr| disr.span),
+            discriminant: disr.map(|disr| disr.node),
+            span: DUMMY_SPAN,
         })
-    ));
+    )));
 
     named!(pub struct_like_body -> Vec<Field>, do_parse!(
         punct!("{") >>
@@ -122,21 +161,23 @@ pub mod parsing {
         (fields)
     ));
 
-    named!(struct_field -> Field, do_parse!(
+    named!(struct_field -> Field, add_span!(do_parse!(
         attrs: many0!(outer_attr) >>
         vis: visibility >>
         id: ident >>
-        punct!(":") >>
+        colon: spanned!(punct!(":")) >>
         ty: ty >>
         (Field {
             ident: Some(id),
             vis: vis,
             attrs: attrs,
             ty: ty,
+            colon_span: Some(colon.span),
+            span: DUMMY_SPAN,
         })
-    ));
+    )));
 
-    named!(tuple_field -> Field, do_parse!(
+    named!(tuple_field -> Field, add_span!(do_parse!(
         attrs: many0!(outer_attr) >>
         vis: visibility >>
         ty: ty >>
@@ -145,29 +186,33 @@ pub mod parsing {
             vis: vis,
             attrs: attrs,
             ty: ty,
+            colon_span: None,
+            span: DUMMY_SPAN,
         })
-    ));
+    )));
 
     named!(pub visibility -> Visibility, alt!(
-        do_parse!(
-            keyword!("pub") >>
-            punct!("(") >>
-            keyword!("crate") >>
-            punct!(")") >>
-            (Visibility::Crate)
-        )
-        |
-        do_parse!(
-            keyword!("pub") >>
-            punct!("(") >>
-            restricted: path >>
-            punct!(")") >>
-            (Visibility::Restricted(Box::new(restricted)))
-        )
-        |
-        keyword!("pub") => { |_| Visibility::Public }
+        map!(spanned!(alt!(
+            do_parse!(
+                keyword!("pub") >>
+                punct!("(") >>
+                keyword!("crate") >>
+                punct!(")") >>
+                (VisibilityKind::Crate)
+            )
+            |
+            do_parse!(
+                keyword!("pub") >>
+                punct!("(") >>
+                restricted: path >>
+                punct!(")") >>
+                (VisibilityKind::Restricted(Box::new(restricted)))
+            )
+            |
+            keyword!("pub") => { |_| VisibilityKind::Public }
+        )), <Visibility as From<Spanned<VisibilityKind>>>::from)
         |
-        epsilon!() => { |_| Visibility::Inherited }
+        epsilon!() => { |_| VisibilityKind::Inherited.into() }
     ));
 }
 
@@ -224,21 +269,21 @@ mod printing {
 
     impl ToTokens for Visibility {
         fn to_tokens(&self, tokens: &mut Tokens) {
-            match *self {
-                Visibility::Public => tokens.append("pub"),
-                Visibility::Crate => {
+            match self.node {
+                VisibilityKind::Public => tokens.append("pub"),
+                VisibilityKind::Crate => {
                     tokens.append("pub");
                     tokens.append("(");
                     tokens.append("crate");
                     tokens.append(")");
                 }
-                Visibility::Restricted(ref path) => {
+                VisibilityKind::Restricted(ref path) => {
                     tokens.append("pub");
                     tokens.append("(");
                     path.to_tokens(tokens);
                     tokens.append(")");
                 }
-                Visibility::Inherited => {}
+                VisibilityKind::Inherited => {}
             }
         }
     }
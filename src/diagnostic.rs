@@ -0,0 +1,549 @@
+use std::cmp;
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::iter;
+
+use {Loc, SourceFile, SourceMap, Span};
+
+/// How serious a `Diagnostic` is.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl Severity {
+    fn style(self) -> &'static str {
+        match self {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+            Severity::Note => GREEN,
+            Severity::Help => CYAN,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match *self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        })
+    }
+}
+
+const RESET: &'static str = "\x1b[0m";
+const BOLD: &'static str = "\x1b[1m";
+const RED: &'static str = "\x1b[1;31m";
+const YELLOW: &'static str = "\x1b[1;33m";
+const GREEN: &'static str = "\x1b[1;32m";
+const CYAN: &'static str = "\x1b[1;36m";
+const BLUE: &'static str = "\x1b[1;34m";
+
+/// A message about a span of source code, which can be rendered together with
+/// the annotated source lines in the style of rustc:
+///
+/// ```text
+/// error: mismatched types
+///  --> src/lib.rs:2:17
+///   |
+/// 2 |     let x: u8 = "a";
+///   |            --   ^^^ expected `u8`
+///   |            |
+///   |            expected due to this
+///   |
+///   = note: string literals have type `&str`
+/// ```
+///
+/// The primary span is underlined with `^` and secondary spans with `-`.
+/// Spans covering several lines are drawn with a line in the margin.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: String,
+    primary: Annotation,
+    secondary: Vec<Annotation>,
+    children: Vec<(Severity, String)>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Annotation {
+    span: Span,
+    label: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new<M: Into<String>>(severity: Severity, message: M, span: Span) -> Self {
+        Diagnostic {
+            severity: severity,
+            message: message.into(),
+            primary: Annotation {
+                span: span,
+                label: None,
+            },
+            secondary: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn error<M: Into<String>>(message: M, span: Span) -> Self {
+        Diagnostic::new(Severity::Error, message, span)
+    }
+
+    pub fn warning<M: Into<String>>(message: M, span: Span) -> Self {
+        Diagnostic::new(Severity::Warning, message, span)
+    }
+
+    /// Sets the label shown next to the primary span.
+    pub fn label<L: Into<String>>(mut self, label: L) -> Self {
+        self.primary.label = Some(label.into());
+        self
+    }
+
+    /// Adds a secondary span, with a label explaining how it relates to the
+    /// primary span.
+    pub fn secondary<L: Into<String>>(mut self, span: Span, label: L) -> Self {
+        self.secondary.push(Annotation {
+            span: span,
+            label: Some(label.into()),
+        });
+        self
+    }
+
+    /// Adds a note, shown after the source snippet.
+    pub fn note<N: Into<String>>(mut self, note: N) -> Self {
+        self.children.push((Severity::Note, note.into()));
+        self
+    }
+
+    /// Adds a help message, shown after the source snippet.
+    pub fn help<H: Into<String>>(mut self, help: H) -> Self {
+        self.children.push((Severity::Help, help.into()));
+        self
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The primary span.
+    pub fn span(&self) -> Span {
+        self.primary.span
+    }
+
+    /// Renders the diagnostic as plain text, showing the source lines from the
+    /// file containing the primary span.
+    ///
+    /// Secondary spans outside of that file are not shown. If the primary span
+    /// is not in any file, only the message and notes are rendered.
+    pub fn render(&self, map: &SourceMap) -> String {
+        self.render_with(map, false)
+    }
+
+    /// Like `render`, but coloured with ANSI escape codes for display on a
+    /// terminal.
+    pub fn render_ansi(&self, map: &SourceMap) -> String {
+        self.render_with(map, true)
+    }
+
+    fn render_with(&self, map: &SourceMap, color: bool) -> String {
+        let painted = |style: &str, text: &str| paint(color, style, text);
+        let mut out = vec![format!("{}{}",
+                                   painted(self.severity.style(), &self.severity.to_string()),
+                                   painted(BOLD, &format!(": {}", self.message)))];
+
+        let file = match map.lookup_file(self.primary.span) {
+            Some(file) => file,
+            None => {
+                for &(severity, ref child) in &self.children {
+                    out.push(format!("{} {}: {}",
+                                     painted(BLUE, "="),
+                                     painted(BOLD, &severity.to_string()),
+                                     child));
+                }
+                return out.join("\n");
+            }
+        };
+
+        let mut marks = vec![Mark::new(file, &self.primary, Some(self.severity.style()))];
+        for annotation in &self.secondary {
+            if file.contains(annotation.span) {
+                marks.push(Mark::new(file, annotation, None));
+            }
+        }
+
+        let mut lines = BTreeSet::new();
+        for mark in &marks {
+            lines.insert(mark.lo.line);
+            if mark.is_multiline() {
+                lines.insert(mark.lo.line + 1);
+                lines.insert(mark.hi.line - 1);
+                lines.insert(mark.hi.line);
+            }
+        }
+        let number_width = lines.iter().next_back().unwrap().to_string().len();
+        let pad = iter::repeat(" ").take(number_width).collect::<String>();
+
+        let name = match file.name() {
+            "" => String::new(),
+            name => format!("{}:", name),
+        };
+        let primary = &marks[0];
+        out.push(format!("{}{} {}{}:{}",
+                         pad,
+                         painted(BLUE, "-->"),
+                         name,
+                         primary.lo.line,
+                         primary.lo.col_char + 1));
+        out.push(format!("{} {}", pad, painted(BLUE, "|")));
+
+        // Spans covering several lines each get a column in the margin
+        let mut multiline: Vec<&Mark> = marks.iter().filter(|mark| mark.is_multiline()).collect();
+        multiline.sort_by_key(|mark| (mark.lo.line, mark.lo.col));
+        let margin = if multiline.is_empty() {
+            0
+        } else {
+            multiline.len() + 1
+        };
+        let mut open = vec![false; multiline.len()];
+
+        let mut prev = None;
+        for &line in &lines {
+            if let Some(prev) = prev {
+                if line > prev + 1 {
+                    out.push(painted(BLUE, "..."));
+                }
+            }
+            prev = Some(line);
+
+            let text = file.line(line).unwrap().replace("\t", " ");
+            let mut row = Row::new();
+            for (depth, mark) in multiline.iter().enumerate() {
+                if mark.lo.line == line && mark.starts_line(&text) {
+                    open[depth] = true;
+                    row.put(depth, "/", mark.style());
+                } else if open[depth] {
+                    row.put(depth, "|", mark.style());
+                }
+            }
+            row.put(margin, &text, "");
+            let number = format!("{:>1$}", line, number_width);
+            out.push(row.render_after(&painted(BLUE, &number), &painted(BLUE, "|"), color));
+
+            let mut rows = Vec::new();
+
+            let mut single: Vec<&Mark> = marks.iter()
+                .filter(|mark| !mark.is_multiline() && mark.lo.line == line)
+                .collect();
+            if !single.is_empty() {
+                single.sort_by_key(|mark| (mark.lo.col_char, mark.hi.col_char));
+                let gutter = gutter(&multiline, &open);
+
+                // Draw secondary underlines first so that the primary one
+                // takes precedence where they overlap.
+                let mut underline = gutter.clone();
+                let mut end = 0;
+                for mark in single.iter().filter(|mark| mark.style.is_none())
+                    .chain(single.iter().filter(|mark| mark.style.is_some())) {
+                    let width = cmp::max(mark.hi.col_char - mark.lo.col_char, 1);
+                    underline.put(margin + mark.lo.col_char,
+                                  &iter::repeat(mark.marker()).take(width).collect::<String>(),
+                                  mark.style());
+                    end = cmp::max(end, mark.lo.col_char + width);
+                }
+
+                let labeled: Vec<&&Mark> = single.iter().filter(|mark| mark.label.is_some()).collect();
+                let (inline, pending) = match labeled.split_last() {
+                    Some((last, rest)) if single.last().unwrap().lo.col_char == last.lo.col_char => {
+                        (Some(last), rest)
+                    }
+                    _ => (None, &labeled[..]),
+                };
+                if let Some(mark) = inline {
+                    underline.put(margin + end + 1, mark.label.unwrap(), mark.style());
+                }
+                rows.push(underline);
+
+                if !pending.is_empty() {
+                    let mut connectors = gutter.clone();
+                    for mark in pending {
+                        connectors.put(margin + mark.lo.col_char, "|", mark.style());
+                    }
+                    rows.push(connectors);
+                    for (i, mark) in pending.iter().enumerate().rev() {
+                        let mut row = gutter.clone();
+                        for left in &pending[..i] {
+                            row.put(margin + left.lo.col_char, "|", left.style());
+                        }
+                        row.put(margin + mark.lo.col_char, mark.label.unwrap(), mark.style());
+                        rows.push(row);
+                    }
+                }
+            }
+
+            for (depth, mark) in multiline.iter().enumerate() {
+                if mark.lo.line == line && !open[depth] {
+                    let mut row = gutter(&multiline, &open);
+                    let underscores = iter::repeat("_")
+                        .take(margin - depth - 1 + mark.lo.col_char)
+                        .collect::<String>();
+                    row.put(depth + 1, &underscores, mark.style());
+                    row.put(margin + mark.lo.col_char, &mark.marker().to_string(), mark.style());
+                    rows.push(row);
+                    open[depth] = true;
+                }
+            }
+
+            // Inner spans are closed before the spans enclosing them
+            for (depth, mark) in multiline.iter().enumerate().rev() {
+                if mark.hi.line == line {
+                    let mut row = gutter(&multiline, &open);
+                    let col = mark.hi.col_char.saturating_sub(1);
+                    let underscores = iter::repeat("_")
+                        .take(margin - depth - 1 + col)
+                        .collect::<String>();
+                    row.put(depth + 1, &underscores, mark.style());
+                    row.put(margin + col, &mark.marker().to_string(), mark.style());
+                    if let Some(label) = mark.label {
+                        row.put(margin + col + 2, label, mark.style());
+                    }
+                    rows.push(row);
+                    open[depth] = false;
+                }
+            }
+
+            for row in rows {
+                out.push(row.render_after(&pad, &painted(BLUE, "|"), color));
+            }
+        }
+
+        if !self.children.is_empty() {
+            out.push(format!("{} {}", pad, painted(BLUE, "|")));
+        }
+        for &(severity, ref child) in &self.children {
+            out.push(format!("{} {} {}: {}",
+                             pad,
+                             painted(BLUE, "="),
+                             painted(BOLD, &severity.to_string()),
+                             child));
+        }
+        out.join("\n")
+    }
+}
+
+/// Collects errors so that they can all be reported at once, rather than
+/// stopping at the first.
+///
+/// In a procedural macro, the collected errors can be emitted in place of the
+/// macro's output as one `compile_error!` invocation per error:
+///
+/// ```rust,ignore
+/// let mut errors = Errors::new();
+/// for field in fields {
+///     if field.ident.is_none() {
+///         errors.error("tuple fields are not supported", field.span);
+///     }
+/// }
+/// if let Err(tokens) = errors.finish() {
+///     return tokens.parse().unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Errors {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Errors {
+    pub fn new() -> Self {
+        Errors::default()
+    }
+
+    /// Records an error with the given message at `span`.
+    pub fn error<M: Into<String>>(&mut self, message: M, span: Span) {
+        self.diagnostics.push(Diagnostic::error(message, span));
+    }
+
+    /// Records a diagnostic, e.g. a `ParseError`.
+    pub fn push<D: Into<Diagnostic>>(&mut self, diagnostic: D) {
+        self.diagnostics.push(diagnostic.into());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// `Ok` if no errors were recorded, otherwise the `compile_error!`
+    /// invocations reporting them.
+    #[cfg(feature = "printing")]
+    pub fn finish(self) -> Result<(), ::quote::Tokens> {
+        use quote::ToTokens;
+
+        if self.is_empty() {
+            return Ok(());
+        }
+        let mut tokens = ::quote::Tokens::new();
+        self.to_tokens(&mut tokens);
+        Err(tokens)
+    }
+}
+
+impl From<Diagnostic> for Errors {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Errors { diagnostics: vec![diagnostic] }
+    }
+}
+
+/// An annotation resolved to lines and columns within its file.
+struct Mark<'a> {
+    lo: Loc,
+    hi: Loc,
+    label: Option<&'a str>,
+    /// The style of the primary span, or `None` for secondary spans
+    style: Option<&'static str>,
+}
+
+impl<'a> Mark<'a> {
+    fn new(file: &SourceFile, annotation: &'a Annotation, style: Option<&'static str>) -> Self {
+        let lo = file.lookup(annotation.span.lo).unwrap();
+        let mut hi = file.lookup(annotation.span.hi).unwrap();
+        // A span which ends with a newline ends on the line before
+        if hi.line > lo.line && hi.col == 0 {
+            let line = file.line(hi.line - 1).unwrap();
+            hi = Loc {
+                line: hi.line - 1,
+                col: line.len(),
+                col_char: line.chars().count(),
+                col_utf16: line.chars().map(char::len_utf16).sum(),
+            };
+        }
+        Mark {
+            lo: lo,
+            hi: hi,
+            label: annotation.label.as_ref().map(|label| &**label),
+            style: style,
+        }
+    }
+
+    fn is_multiline(&self) -> bool {
+        self.hi.line > self.lo.line
+    }
+
+    /// Whether the span starts at the first non-whitespace char of its line.
+    fn starts_line(&self, text: &str) -> bool {
+        text.chars().take(self.lo.col_char).all(char::is_whitespace)
+    }
+
+    fn marker(&self) -> char {
+        match self.style {
+            Some(_) => '^',
+            None => '-',
+        }
+    }
+
+    fn style(&self) -> &'static str {
+        self.style.unwrap_or(BLUE)
+    }
+}
+
+/// The margin of an annotation row, with a line for each open multi-line span.
+fn gutter(multiline: &[&Mark], open: &[bool]) -> Row {
+    let mut row = Row::new();
+    for (depth, mark) in multiline.iter().enumerate() {
+        if open[depth] {
+            row.put(depth, "|", mark.style());
+        }
+    }
+    row
+}
+
+/// A line of output built up from styled text placed at given columns.
+#[derive(Clone)]
+struct Row {
+    chars: Vec<(char, &'static str)>,
+}
+
+impl Row {
+    fn new() -> Self {
+        Row { chars: Vec::new() }
+    }
+
+    fn put(&mut self, col: usize, text: &str, style: &'static str) {
+        for (i, ch) in text.chars().enumerate() {
+            if col + i >= self.chars.len() {
+                self.chars.resize(col + i + 1, (' ', ""));
+            }
+            self.chars[col + i] = (ch, style);
+        }
+    }
+
+    /// Renders the row after the given gutter, without trailing whitespace.
+    fn render_after(&self, number: &str, separator: &str, color: bool) -> String {
+        let mut out = format!("{} {}", number, separator);
+        let len = match self.chars.iter().rposition(|&(ch, _)| ch != ' ') {
+            Some(last) => last + 1,
+            None => return out,
+        };
+        out.push(' ');
+        let mut run = String::new();
+        let mut run_style = "";
+        for &(ch, style) in &self.chars[..len] {
+            if style != run_style {
+                out.push_str(&paint(color, run_style, &run));
+                run.clear();
+                run_style = style;
+            }
+            run.push(ch);
+        }
+        out.push_str(&paint(color, run_style, &run));
+        out
+    }
+}
+
+fn paint(color: bool, style: &str, text: &str) -> String {
+    if color && !style.is_empty() && !text.is_empty() {
+        format!("{}{}{}", style, text, RESET)
+    } else {
+        text.to_owned()
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use super::*;
+    use quote::{Tokens, ToTokens};
+
+    impl ToTokens for Errors {
+        fn to_tokens(&self, tokens: &mut Tokens) {
+            for diagnostic in &self.diagnostics {
+                // Token streams carry no location information, so labels on
+                // spans are dropped. Notes are kept as part of the message.
+                let mut message = diagnostic.message.clone();
+                for &(severity, ref child) in &diagnostic.children {
+                    message.push_str(&format!("\n= {}: {}", severity, child));
+                }
+                tokens.append("compile_error");
+                tokens.append("!");
+                tokens.append("(");
+                message.to_tokens(tokens);
+                tokens.append(")");
+                tokens.append(";");
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::cmp;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::iter;
+
+use synom::{FurthestFailure, ParseState};
+use synom::space::skip_whitespace;
+
+use {Diagnostic, Expected, SourceMap, Span};
+
+/// An error produced when the input could not be parsed as the requested
+/// construct.
+///
+/// The `Display` implementation renders the message together with the
+/// offending source line and a caret pointing at the failing span.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    // Boxed so that the `Result`s returned by the parse entry points stay
+    // small.
+    inner: Box<ErrorInner>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct ErrorInner {
+    construct: &'static str,
+    trailing: bool,
+    span: Span,
+    expected: Vec<Expected>,
+    found: String,
+    line: usize,
+    col: usize,
+    width: usize,
+    source_line: String,
+}
+
+impl ParseError {
+    /// An error for input which could not be parsed as `construct`, failing at
+    /// `span` within `input`.
+    ///
+    /// A span reaching past the end of `input` or splitting a char is clamped
+    /// to the nearest enclosing position within it.
+    pub fn new(construct: &'static str, input: &str, span: Span) -> Self {
+        let span = clamp(input, span);
+        let mut map = SourceMap::new();
+        let file = map.add_file("", input);
+        let file = map.file(file);
+        let lo = file.lookup(span.lo).unwrap();
+        let source_line = file.line(lo.line).unwrap().to_owned();
+        let found = match file.span_text(span) {
+            Some("") | None => "end of input".to_owned(),
+            Some(text) => format!("`{}`", text),
+        };
+        let width = match file.lookup(span.hi) {
+            Some(ref hi) if hi.line == lo.line => hi.col_char - lo.col_char,
+            _ => source_line.chars().count() - lo.col_char,
+        };
+        ParseError {
+            inner: Box::new(ErrorInner {
+                construct: construct,
+                trailing: false,
+                span: span,
+                expected: Vec::new(),
+                found: found,
+                line: lo.line,
+                col: lo.col_char,
+                width: width,
+                source_line: source_line,
+            }),
+        }
+    }
+
+    /// An error for input in which `construct` was parsed successfully but
+    /// was followed by unparsed tokens starting at `span`.
+    pub fn trailing(construct: &'static str, input: &str, span: Span) -> Self {
+        let mut err = ParseError::new(construct, input, span);
+        err.inner.trailing = true;
+        err
+    }
+
+    /// Sets the tokens or constructs which would have been accepted at the
+    /// failing position.
+    pub fn with_expected(mut self, expected: Vec<Expected>) -> Self {
+        self.inner.expected = expected;
+        self
+    }
+
+    /// The span at which parsing failed.
+    pub fn span(&self) -> Span {
+        self.inner.span
+    }
+
+    /// The name of the construct being parsed, e.g. `"type"`.
+    pub fn construct(&self) -> &'static str {
+        self.inner.construct
+    }
+
+    /// The tokens or constructs which would have been accepted at the failing
+    /// position, if known.
+    pub fn expected(&self) -> &[Expected] {
+        &self.inner.expected
+    }
+
+    /// The 1-based line on which parsing failed.
+    pub fn line(&self) -> usize {
+        self.inner.line
+    }
+
+    /// The 0-based column, in chars, at which parsing failed.
+    pub fn column(&self) -> usize {
+        self.inner.col
+    }
+
+    fn message(&self) -> String {
+        let inner = &self.inner;
+        let mut message = if inner.trailing {
+            format!("unparsed tokens after {}", inner.construct)
+        } else {
+            format!("failed to parse {}", inner.construct)
+        };
+        if let Some((last, rest)) = inner.expected.split_last() {
+            message.push_str(", expected ");
+            if !rest.is_empty() {
+                let rest: Vec<_> = rest.iter().map(Expected::to_string).collect();
+                message.push_str(&rest.join(", "));
+                message.push_str(" or ");
+            }
+            message.push_str(&format!("{}, found {}", last, inner.found));
+        }
+        message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let inner = &self.inner;
+        let line = inner.line.to_string();
+        let gutter = repeat(" ", line.len());
+        try!(writeln!(formatter, "error: {}", self.message()));
+        try!(writeln!(formatter, "{}--> {}:{}", gutter, inner.line, inner.col + 1));
+        try!(writeln!(formatter, "{} |", gutter));
+        try!(writeln!(formatter, "{} | {}", line, inner.source_line));
+        write!(formatter,
+               "{} | {}{}",
+               gutter,
+               repeat(" ", inner.col),
+               repeat("^", cmp::max(inner.width, 1)))
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(err: ParseError) -> Self {
+        Diagnostic::error(err.message(), err.inner.span)
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        "failed to parse"
+    }
+}
+
+fn repeat(s: &str, n: usize) -> String {
+    iter::repeat(s).take(n).collect()
+}
+
+/// Moves both ends of the span into `input`, widening it to char boundaries.
+fn clamp(input: &str, span: Span) -> Span {
+    let mut lo = cmp::min(span.lo, input.len());
+    let mut hi = cmp::min(cmp::max(span.hi, lo), input.len());
+    while !input.is_char_boundary(lo) {
+        lo -= 1;
+    }
+    while !input.is_char_boundary(hi) {
+        hi += 1;
+    }
+    Span { lo: lo, hi: hi }
+}
+
+/// Builds the error for a parser which was run on `start` and either failed,
+/// or succeeded but left `rest` unparsed.
+///
+/// The error is reported where the parser got furthest, along with whatever
+/// it would have accepted there, unless that lies before `rest`.
+pub fn from_failure(construct: &'static str,
+                    input: &str,
+                    start: ParseState,
+                    rest: Option<ParseState>,
+                    failure: &FurthestFailure)
+                    -> ParseError {
+    let start = skip_whitespace(start);
+    let rest = skip_whitespace(rest.unwrap_or(start));
+    let (at, expected) = match failure.position() {
+        Some(furthest) if furthest >= rest.idx() => {
+            (rest.advance(furthest - rest.idx()), failure.expected())
+        }
+        _ => (rest, Vec::new()),
+    };
+    let err = if at.idx() == rest.idx() && rest.idx() > start.idx() {
+        ParseError::trailing(construct, input, next_token(at))
+    } else {
+        ParseError::new(construct, input, next_token(at))
+    };
+    err.with_expected(expected)
+}
+
+/// The span of the next token of the input: a whole word, or otherwise a
+/// single char. At the end of the input the span is empty.
+fn next_token(input: ParseState) -> Span {
+    let word = input.chars()
+        .take_while(|&ch| ch == '_' || ch.is_alphanumeric())
+        .map(char::len_utf8)
+        .sum();
+    let len = match word {
+        0 => input.chars().next().map_or(0, char::len_utf8),
+        word => word,
+    };
+    Span {
+        lo: input.idx(),
+        hi: input.idx() + len,
+    }
+}
@@ -93,10 +93,14 @@ pub enum ExprKind {
     Loop(Block, Option<Ident>),
     /// A `match` block.
     Match(Box<Expr>, Vec<Arm>),
-    /// A closure (for example, `move |a, b, c| a + b + c`)
-    Closure(CaptureBy, Box<FnDecl>, Box<Expr>),
+    /// A closure (for example, `move |a, b, c| a + b + c` or `async || x.await`)
+    Closure(CaptureBy, Asyncness, Box<FnDecl>, Box<Expr>),
     /// A block (`{ ... }` or `unsafe { ... }`)
     Block(Unsafety, Block),
+    /// An async block (`async { ... }` or `async move { ... }`)
+    Async(CaptureBy, Block),
+    /// An await of a future (`fut.await`)
+    Await(Box<Expr>),
 
     /// An assignment (`a = foo()`)
     Assign(Box<Expr>, Box<Expr>),
@@ -174,10 +178,34 @@ pub struct FieldValue {
 pub struct Block {
     /// Statements in a block
     pub stmts: Vec<Stmt>,
+    /// The span from `{` to `}`
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub enum Stmt {
+pub struct Stmt {
+    pub node: StmtKind,
+    /// The span of the statement, including its trailing `;` if any
+    pub span: Span,
+}
+
+impl Stmt {
+    pub fn new(node: StmtKind, span: Span) -> Stmt {
+        Stmt {
+            node: node,
+            span: span,
+        }
+    }
+}
+
+impl From<Spanned<StmtKind>> for Stmt {
+    fn from(spanned: Spanned<StmtKind>) -> Stmt {
+        Stmt::new(spanned.node, spanned.span)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum StmtKind {
     /// A local (let) binding.
     Local(Box<Local>),
 
@@ -192,6 +220,12 @@ pub enum Stmt {
     Mac(Box<(Mac, MacStmtStyle, Vec<Attribute>)>),
 }
 
+impl StmtKind {
+    pub fn span(self, span: Span) -> Stmt {
+        Stmt::new(self, span)
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum MacStmtStyle {
     /// The macro statement had a trailing semicolon, e.g. `foo! { ... };`
@@ -213,17 +247,40 @@ pub struct Local {
     /// Initializer expression to set the value, if any
     pub init: Option<Box<Expr>>,
     pub attrs: Vec<Attribute>,
+    /// The span from the first attribute or `let` to the `;`
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Pat {
+    pub node: PatKind,
+    pub span: Span,
+}
+
+impl Pat {
+    pub fn new(node: PatKind, span: Span) -> Pat {
+        Pat {
+            node: node,
+            span: span,
+        }
+    }
+}
+
+impl From<Spanned<PatKind>> for Pat {
+    fn from(spanned: Spanned<PatKind>) -> Pat {
+        Pat::new(spanned.node, spanned.span)
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 // Clippy false positive
 // https://github.com/Manishearth/rust-clippy/issues/1241
 #[cfg_attr(feature = "clippy", allow(enum_variant_names))]
-pub enum Pat {
+pub enum PatKind {
     /// Represents a wildcard pattern (`_`)
     Wild,
 
-    /// A `Pat::Ident` may either be a new bound variable (`ref mut binding @ OPT_SUBPATTERN`),
+    /// A `PatKind::Ident` may either be a new bound variable (`ref mut binding @ OPT_SUBPATTERN`),
     /// or a unit struct/variant pattern, or a const pattern (in the last two cases the third
     /// field must be `None`). Disambiguation cannot be done with parser alone, so it happens
     /// during name resolution.
@@ -257,12 +314,18 @@ pub enum Pat {
     /// A range pattern, e.g. `1...2`
     Range(Box<Expr>, Box<Expr>),
     /// `[a, b, ..i, y, z]` is represented as:
-    ///     `Pat::Slice(box [a, b], Some(i), box [y, z])`
+    ///     `PatKind::Slice(box [a, b], Some(i), box [y, z])`
     Slice(Vec<Pat>, Option<Box<Pat>>, Vec<Pat>),
     /// A macro pattern; pre-expansion
     Mac(Mac),
 }
 
+impl PatKind {
+    pub fn span(self, span: Span) -> Pat {
+        Pat::new(self, span)
+    }
+}
+
 /// An arm of a 'match'.
 ///
 /// E.g. `0...10 => { println!("match!") }` as in
@@ -278,7 +341,11 @@ pub struct Arm {
     pub attrs: Vec<Attribute>,
     pub pats: Vec<Pat>,
     pub guard: Option<Box<Expr>>,
+    /// The span of the `=>` between the patterns and the body
+    pub fat_arrow_span: Span,
     pub body: Box<Expr>,
+    /// The span from the first pattern to the end of the body
+    pub span: Span,
 }
 
 /// A capture clause
@@ -310,6 +377,7 @@ pub struct FieldPat {
     pub pat: Box<Pat>,
     pub is_shorthand: bool,
     pub attrs: Vec<Attribute>,
+    pub span: Span,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -326,7 +394,7 @@ pub mod parsing {
     use attr::parsing::outer_attr;
     use generics::parsing::lifetime;
     use ident::parsing::{ident, wordlike};
-    use item::parsing::item;
+    use item::parsing::{asyncness, item};
     use lit::parsing::{digits, lit};
     use mac::parsing::{mac, token_trees};
     use synom::IResult::{self, Error};
@@ -350,7 +418,7 @@ pub mod parsing {
         };
     }
 
-    named!(pub expr -> Expr, ambiguous_expr!(true));
+    named!(pub expr -> Expr, label!("expression", ambiguous_expr!(true)));
 
     named!(expr_no_struct -> Expr, ambiguous_expr!(false));
 
@@ -395,6 +463,8 @@ pub mod parsing {
                 |
                 call!(expr_closure, allow_struct)
                 |
+                expr_async
+                |
                 cond_reduce!(allow_block, expr_block)
                 |
                 call!(expr_range, allow_struct)
@@ -467,6 +537,14 @@ pub mod parsing {
                     }.into();
                 })
                 |
+                tap!(kw: spanned!(and_await) => {
+                    let span = e.span;
+                    e = Spanned {
+                        node: ExprKind::Await(Box::new(e)),
+                        span: span.extend(kw.span),
+                    }.into();
+                })
+                |
                 tap!(field: spanned!(and_field) => {
                     let span = e.span;
                     e = Spanned {
@@ -536,6 +614,7 @@ pub mod parsing {
             Box::new(place),
             Box::new(ExprKind::Block(Unsafety::Normal, Block {
                 stmts: value.node,
+                span: value.span,
             }).span(value.span)),
         ))
     ));
@@ -691,9 +770,9 @@ pub mod parsing {
 
     named!(match_arm -> Arm, do_parse!(
         attrs: many0!(outer_attr) >>
-        pats: separated_nonempty_list!(punct!("|"), pat) >>
+        pats: spanned!(separated_nonempty_list!(punct!("|"), pat)) >>
         guard: option!(preceded!(keyword!("if"), expr)) >>
-        punct!("=>") >>
+        fat_arrow: spanned!(punct!("=>")) >>
         body: alt!(
             map!(spanned!(block),
                  |Spanned{ node, span }| ExprKind::Block(Unsafety::Normal, node).span(span))
@@ -701,44 +780,57 @@ pub mod parsing {
             expr
         ) >>
         (Arm {
+            span: pats.span.extend(body.span),
             attrs: attrs,
-            pats: pats,
+            pats: pats.node,
             guard: guard.map(Box::new),
+            fat_arrow_span: fat_arrow.span,
             body: Box::new(body),
         })
     ));
 
     named_ambiguous_expr!(expr_closure -> ExprKind, allow_struct, do_parse!(
+        asyncness: asyncness >>
         capture: capture_by >>
         punct!("|") >>
         inputs: terminated_list!(punct!(","), closure_arg) >>
         punct!("|") >>
         ret_and_body: alt!(
             do_parse!(
-                punct!("->") >>
+                rarrow: spanned!(punct!("->")) >>
                 ty: ty >>
                 body: spanned!(block) >>
                 (FunctionRetTy::Ty(ty),
+                 Some(rarrow.span),
                  ExprKind::Block(Unsafety::Normal, body.node).span(body.span))
             )
             |
-            map!(ambiguous_expr!(allow_struct), |e| (FunctionRetTy::Default, e))
+            map!(ambiguous_expr!(allow_struct), |e| (FunctionRetTy::Default, None, e))
         ) >>
         (ExprKind::Closure(
             capture,
+            asyncness,
             Box::new(FnDecl {
                 inputs: inputs,
                 output: ret_and_body.0,
+                rarrow_span: ret_and_body.1,
                 variadic: false,
             }),
-            Box::new(ret_and_body.1),
+            Box::new(ret_and_body.2),
         ))
     ));
 
     named!(closure_arg -> FnArg, do_parse!(
         pat: pat >>
         ty: option!(preceded!(punct!(":"), ty)) >>
-        (FnArg::Captured(pat, ty.unwrap_or(Ty::Infer)))
+        ({
+            // An omitted type is located just after the pattern
+            let infer = Span {
+                lo: pat.span.hi,
+                hi: pat.span.hi,
+            };
+            FnArg::Captured(pat, ty.unwrap_or_else(|| TyKind::Infer.span(infer)))
+        })
     ));
 
     named!(expr_while -> ExprKind, do_parse!(
@@ -808,11 +900,28 @@ pub mod parsing {
             })
         )
         |
-        map!(spanned!(ident), |Spanned{ node, span }: Spanned<Ident>| FieldValue {
-            ident: node.clone(),
-            expr: ExprKind::Path(None, node.into()).span(span),
-            is_shorthand: true,
-            attrs: Vec::new(),
+        map!(spanned!(ident), |Spanned{ node, span }: Spanned<Ident>| {
+            let path = Path {
+                global: false,
+                segments: vec![PathSegment {
+                    ident: node.clone(),
+                    parameters: PathParameters::AngleBracketed(AngleBracketedParameterData {
+                        span: Span {
+                            lo: span.hi,
+                            hi: span.hi,
+                        },
+                        ..AngleBracketedParameterData::default()
+                    }),
+                    span: span,
+                }],
+                span: span,
+            };
+            FieldValue {
+                ident: node,
+                expr: ExprKind::Path(None, path).span(span),
+                is_shorthand: true,
+                attrs: Vec::new(),
+            }
         })
     ));
 
@@ -828,9 +937,14 @@ pub mod parsing {
     named!(expr_block -> ExprKind, do_parse!(
         rules: unsafety >>
         b: block >>
-        (ExprKind::Block(rules, Block {
-            stmts: b.stmts,
-        }))
+        (ExprKind::Block(rules, b))
+    ));
+
+    named!(expr_async -> ExprKind, do_parse!(
+        keyword!("async") >>
+        capture: capture_by >>
+        b: block >>
+        (ExprKind::Async(capture, b))
     ));
 
     named_ambiguous_expr!(expr_range -> ExprKind, allow_struct, do_parse!(
@@ -864,6 +978,8 @@ pub mod parsing {
         ambiguous_expr!(allow_struct)
     ));
 
+    named!(and_await -> &str, preceded!(punct!("."), keyword!("await")));
+
     named!(and_field -> Ident, preceded!(punct!("."), ident));
 
     named!(and_tup_field -> u64, preceded!(punct!("."), digits));
@@ -876,11 +992,14 @@ pub mod parsing {
     ));
 
     named!(pub block -> Block, do_parse!(
-        punct!("{") >>
-        stmts: within_block >>
-        punct!("}") >>
+        stmts: spanned!(delimited!(
+            punct!("{"),
+            within_block,
+            punct!("}")
+        )) >>
         (Block {
-            stmts: stmts,
+            stmts: stmts.node,
+            span: stmts.span,
         })
     ));
 
@@ -891,13 +1010,14 @@ pub mod parsing {
         (match last {
             None => standalone,
             Some(last) => {
-                standalone.push(Stmt::Expr(Box::new(last)));
+                let span = last.span;
+                standalone.push(StmtKind::Expr(Box::new(last)).span(span));
                 standalone
             }
         })
     ));
 
-    named!(standalone_stmt -> Stmt, alt!(
+    named!(standalone_stmt -> Stmt, map!(spanned!(alt!(
         stmt_mac
         |
         stmt_local
@@ -905,9 +1025,9 @@ pub mod parsing {
         stmt_item
         |
         stmt_expr
-    ));
+    )), <Stmt as From<Spanned<StmtKind>>>::from));
 
-    named!(stmt_mac -> Stmt, do_parse!(
+    named!(stmt_mac -> StmtKind, do_parse!(
         attrs: many0!(outer_attr) >>
         what: path >>
         punct!("!") >>
@@ -919,7 +1039,7 @@ pub mod parsing {
             punct!("}")
         )) >>
         semi: option!(punct!(";")) >>
-        (Stmt::Mac(Box::new((
+        (StmtKind::Mac(Box::new((
             Mac {
                 path: what,
                 tts: vec![TokenTree::Delimited(Delimited {
@@ -936,22 +1056,23 @@ pub mod parsing {
         ))))
     ));
 
-    named!(stmt_local -> Stmt, do_parse!(
+    named!(stmt_local -> StmtKind, map!(add_span!(do_parse!(
         attrs: many0!(outer_attr) >>
         keyword!("let") >>
         pat: pat >>
         ty: option!(preceded!(punct!(":"), ty)) >>
         init: option!(preceded!(punct!("="), expr)) >>
         punct!(";") >>
-        (Stmt::Local(Box::new(Local {
+        (Local {
             pat: Box::new(pat),
             ty: ty.map(Box::new),
             init: init.map(Box::new),
             attrs: attrs,
-        })))
-    ));
+            span: DUMMY_SPAN,
+        })
+    )), |local| StmtKind::Local(Box::new(local))));
 
-    named!(stmt_item -> Stmt, map!(item, |i| Stmt::Item(Box::new(i))));
+    named!(stmt_item -> StmtKind, map!(item, |i| StmtKind::Item(Box::new(i))));
 
     fn requires_semi(e: &Expr) -> bool {
         match e.node {
@@ -968,23 +1089,23 @@ pub mod parsing {
         }
     }
 
-    named!(stmt_expr -> Stmt, do_parse!(
+    named!(stmt_expr -> StmtKind, do_parse!(
         attrs: many0!(outer_attr) >>
         mut e: expr >>
         semi: option!(punct!(";")) >>
         ({
             e.attrs = attrs;
             if semi.is_some() {
-                Stmt::Semi(Box::new(e))
+                StmtKind::Semi(Box::new(e))
             } else if requires_semi(&e) {
                 return Error;
             } else {
-                Stmt::Expr(Box::new(e))
+                StmtKind::Expr(Box::new(e))
             }
         })
     ));
 
-    named!(pub pat -> Pat, alt!(
+    named!(pub pat -> Pat, label!("pattern", map!(spanned!(alt!(
         pat_wild // must be before pat_ident
         |
         pat_box // must be before pat_ident
@@ -1008,30 +1129,30 @@ pub mod parsing {
         pat_ref
         |
         pat_slice
-    ));
+    )), <Pat as From<Spanned<PatKind>>>::from)));
 
-    named!(pat_mac -> Pat, map!(mac, Pat::Mac));
+    named!(pat_mac -> PatKind, map!(mac, PatKind::Mac));
 
-    named!(pat_wild -> Pat, map!(keyword!("_"), |_| Pat::Wild));
+    named!(pat_wild -> PatKind, map!(keyword!("_"), |_| PatKind::Wild));
 
-    named!(pat_box -> Pat, do_parse!(
+    named!(pat_box -> PatKind, do_parse!(
         keyword!("box") >>
         pat: pat >>
-        (Pat::Box(Box::new(pat)))
+        (PatKind::Box(Box::new(pat)))
     ));
 
-    named!(pat_ident -> Pat, do_parse!(
+    named!(pat_ident -> PatKind, do_parse!(
         mode: option!(keyword!("ref")) >>
         mutability: mutability >>
         name: alt!(
             ident
             |
-            keyword!("self") => { Into::into }
+            spanned!(keyword!("self")) => { |s: Spanned<&str>| Ident::with_span(s.node, s.span) }
         ) >>
         not!(punct!("<")) >>
         not!(punct!("::")) >>
         subpat: option!(preceded!(punct!("@"), pat)) >>
-        (Pat::Ident(
+        (PatKind::Ident(
             if mode.is_some() {
                 BindingMode::ByRef(mutability)
             } else {
@@ -1042,13 +1163,13 @@ pub mod parsing {
         ))
     ));
 
-    named!(pat_tuple_struct -> Pat, do_parse!(
+    named!(pat_tuple_struct -> PatKind, do_parse!(
         path: path >>
         tuple: pat_tuple_helper >>
-        (Pat::TupleStruct(path, tuple.0, tuple.1))
+        (PatKind::TupleStruct(path, tuple.0, tuple.1))
     ));
 
-    named!(pat_struct -> Pat, do_parse!(
+    named!(pat_struct -> PatKind, do_parse!(
         path: path >>
         punct!("{") >>
         fields: separated_list!(punct!(","), field_pat) >>
@@ -1058,10 +1179,10 @@ pub mod parsing {
         )) >>
         cond!(!fields.is_empty() && more.is_none(), option!(punct!(","))) >>
         punct!("}") >>
-        (Pat::Struct(path, fields, more.is_some()))
+        (PatKind::Struct(path, fields, more.is_some()))
     ));
 
-    named!(field_pat -> FieldPat, alt!(
+    named!(field_pat -> FieldPat, add_span!(alt!(
         do_parse!(
             ident: wordlike >>
             punct!(":") >>
@@ -1071,16 +1192,20 @@ pub mod parsing {
                 pat: Box::new(pat),
                 is_shorthand: false,
                 attrs: Vec::new(),
+                span: DUMMY_SPAN,
             })
         )
         |
         do_parse!(
-            boxed: option!(keyword!("box")) >>
-            mode: option!(keyword!("ref")) >>
-            mutability: mutability >>
-            ident: ident >>
+            boxed: option!(spanned!(keyword!("box"))) >>
+            binding: spanned!(tuple!(
+                option!(keyword!("ref")),
+                mutability,
+                ident
+            )) >>
             ({
-                let mut pat = Pat::Ident(
+                let Spanned { node: (mode, mutability, ident), span } = binding;
+                let mut pat = PatKind::Ident(
                     if mode.is_some() {
                         BindingMode::ByRef(mutability)
                     } else {
@@ -1088,25 +1213,26 @@ pub mod parsing {
                     },
                     ident.clone(),
                     None,
-                );
-                if boxed.is_some() {
-                    pat = Pat::Box(Box::new(pat));
+                ).span(span);
+                if let Some(boxed) = boxed {
+                    pat = PatKind::Box(Box::new(pat)).span(boxed.span.extend(span));
                 }
                 FieldPat {
                     ident: ident,
                     pat: Box::new(pat),
                     is_shorthand: true,
                     attrs: Vec::new(),
+                    span: DUMMY_SPAN,
                 }
             })
         )
-    ));
+    )));
 
-    named!(pat_path -> Pat, map!(qpath, |(qself, path)| Pat::Path(qself, path)));
+    named!(pat_path -> PatKind, map!(qpath, |(qself, path)| PatKind::Path(qself, path)));
 
-    named!(pat_tuple -> Pat, map!(
+    named!(pat_tuple -> PatKind, map!(
         pat_tuple_helper,
-        |(pats, dotdot)| Pat::Tuple(pats, dotdot)
+        |(pats, dotdot)| PatKind::Tuple(pats, dotdot)
     ));
 
     named!(pat_tuple_helper -> (Vec<Pat>, Option<usize>), do_parse!(
@@ -1131,27 +1257,27 @@ pub mod parsing {
         })
     ));
 
-    named!(pat_ref -> Pat, do_parse!(
+    named!(pat_ref -> PatKind, do_parse!(
         punct!("&") >>
         mutability: mutability >>
         pat: pat >>
-        (Pat::Ref(Box::new(pat), mutability))
+        (PatKind::Ref(Box::new(pat), mutability))
     ));
 
-    named!(pat_lit -> Pat, do_parse!(
+    named!(pat_lit -> PatKind, do_parse!(
         lit: pat_lit_expr >>
         (if let ExprKind::Path(_, _) = lit.node {
             return IResult::Error; // these need to be parsed by pat_path
         } else {
-            Pat::Lit(Box::new(lit))
+            PatKind::Lit(Box::new(lit))
         })
     ));
 
-    named!(pat_range -> Pat, do_parse!(
+    named!(pat_range -> PatKind, do_parse!(
         lo: pat_lit_expr >>
         punct!("...") >>
         hi: pat_lit_expr >>
-        (Pat::Range(Box::new(lo), Box::new(hi)))
+        (PatKind::Range(Box::new(lo), Box::new(hi)))
     ));
 
     named!(pat_lit_expr -> Expr, map!(spanned!(do_parse!(
@@ -1168,29 +1294,29 @@ pub mod parsing {
         })
     )), <Expr as From<Spanned<ExprKind>>>::from));
 
-    named!(pat_slice -> Pat, do_parse!(
+    named!(pat_slice -> PatKind, do_parse!(
         punct!("[") >>
         mut before: separated_list!(punct!(","), pat) >>
         after: option!(do_parse!(
             comma_before_dots: option!(cond_reduce!(!before.is_empty(), punct!(","))) >>
-            punct!("..") >>
+            dots: spanned!(punct!("..")) >>
             after: many0!(preceded!(punct!(","), pat)) >>
             cond!(!after.is_empty(), option!(punct!(","))) >>
-            (comma_before_dots.is_some(), after)
+            (comma_before_dots.is_some(), dots.span, after)
         )) >>
         cond!(after.is_none(), option!(punct!(","))) >>
         punct!("]") >>
         (match after {
-            None => Pat::Slice(before, None, Vec::new()),
-            Some((true, after)) => {
+            None => PatKind::Slice(before, None, Vec::new()),
+            Some((true, dots, after)) => {
                 if before.is_empty() {
                     return IResult::Error;
                 }
-                Pat::Slice(before, Some(Box::new(Pat::Wild)), after)
+                PatKind::Slice(before, Some(Box::new(PatKind::Wild.span(dots))), after)
             }
-            Some((false, after)) => {
-                let rest = before.pop().unwrap_or(Pat::Wild);
-                Pat::Slice(before, Some(Box::new(rest)), after)
+            Some((false, dots, after)) => {
+                let rest = before.pop().unwrap_or_else(|| PatKind::Wild.span(dots));
+                PatKind::Slice(before, Some(Box::new(rest)), after)
             }
         })
     ));
@@ -1345,7 +1471,8 @@ mod printing {
                     tokens.append_all(arms);
                     tokens.append("}");
                 }
-                ExprKind::Closure(capture, ref decl, ref expr) => {
+                ExprKind::Closure(capture, asyncness, ref decl, ref expr) => {
+                    asyncness.to_tokens(tokens);
                     capture.to_tokens(tokens);
                     tokens.append("|");
                     for (i, input) in decl.inputs.iter().enumerate() {
@@ -1353,7 +1480,7 @@ mod printing {
                             tokens.append(",");
                         }
                         match *input {
-                            FnArg::Captured(ref pat, Ty::Infer) => {
+                            FnArg::Captured(ref pat, Ty { node: TyKind::Infer, .. }) => {
                                 pat.to_tokens(tokens);
                             }
                             _ => input.to_tokens(tokens),
@@ -1373,6 +1500,16 @@ mod printing {
                     rules.to_tokens(tokens);
                     block.to_tokens(tokens);
                 }
+                ExprKind::Async(capture, ref block) => {
+                    tokens.append("async");
+                    capture.to_tokens(tokens);
+                    block.to_tokens(tokens);
+                }
+                ExprKind::Await(ref expr) => {
+                    expr.to_tokens(tokens);
+                    tokens.append(".");
+                    tokens.append("await");
+                }
                 ExprKind::Assign(ref var, ref expr) => {
                     var.to_tokens(tokens);
                     tokens.append("=");
@@ -1514,9 +1651,9 @@ mod printing {
 
     impl ToTokens for Pat {
         fn to_tokens(&self, tokens: &mut Tokens) {
-            match *self {
-                Pat::Wild => tokens.append("_"),
-                Pat::Ident(mode, ref ident, ref subpat) => {
+            match self.node {
+                PatKind::Wild => tokens.append("_"),
+                PatKind::Ident(mode, ref ident, ref subpat) => {
                     mode.to_tokens(tokens);
                     ident.to_tokens(tokens);
                     if let Some(ref subpat) = *subpat {
@@ -1524,7 +1661,7 @@ mod printing {
                         subpat.to_tokens(tokens);
                     }
                 }
-                Pat::Struct(ref path, ref fields, dots) => {
+                PatKind::Struct(ref path, ref fields, dots) => {
                     path.to_tokens(tokens);
                     tokens.append("{");
                     tokens.append_separated(fields, ",");
@@ -1536,7 +1673,7 @@ mod printing {
                     }
                     tokens.append("}");
                 }
-                Pat::TupleStruct(ref path, ref pats, dotpos) => {
+                PatKind::TupleStruct(ref path, ref pats, dotpos) => {
                     path.to_tokens(tokens);
                     tokens.append("(");
                     match dotpos {
@@ -1555,8 +1692,8 @@ mod printing {
                     }
                     tokens.append(")");
                 }
-                Pat::Path(None, ref path) => path.to_tokens(tokens),
-                Pat::Path(Some(ref qself), ref path) => {
+                PatKind::Path(None, ref path) => path.to_tokens(tokens),
+                PatKind::Path(Some(ref qself), ref path) => {
                     tokens.append("<");
                     qself.ty.to_tokens(tokens);
                     if qself.position > 0 {
@@ -1577,7 +1714,7 @@ mod printing {
                         segment.to_tokens(tokens);
                     }
                 }
-                Pat::Tuple(ref pats, dotpos) => {
+                PatKind::Tuple(ref pats, dotpos) => {
                     tokens.append("(");
                     match dotpos {
                         Some(pos) => {
@@ -1600,29 +1737,29 @@ mod printing {
                     }
                     tokens.append(")");
                 }
-                Pat::Box(ref inner) => {
+                PatKind::Box(ref inner) => {
                     tokens.append("box");
                     inner.to_tokens(tokens);
                 }
-                Pat::Ref(ref target, mutability) => {
+                PatKind::Ref(ref target, mutability) => {
                     tokens.append("&");
                     mutability.to_tokens(tokens);
                     target.to_tokens(tokens);
                 }
-                Pat::Lit(ref lit) => lit.to_tokens(tokens),
-                Pat::Range(ref lo, ref hi) => {
+                PatKind::Lit(ref lit) => lit.to_tokens(tokens),
+                PatKind::Range(ref lo, ref hi) => {
                     lo.to_tokens(tokens);
                     tokens.append("...");
                     hi.to_tokens(tokens);
                 }
-                Pat::Slice(ref before, ref rest, ref after) => {
+                PatKind::Slice(ref before, ref rest, ref after) => {
                     tokens.append("[");
                     tokens.append_separated(before, ",");
                     if let Some(ref rest) = *rest {
                         if !before.is_empty() {
                             tokens.append(",");
                         }
-                        if **rest != Pat::Wild {
+                        if rest.node != PatKind::Wild {
                             rest.to_tokens(tokens);
                         }
                         tokens.append("..");
@@ -1633,7 +1770,7 @@ mod printing {
                     }
                     tokens.append("]");
                 }
-                Pat::Mac(ref mac) => mac.to_tokens(tokens),
+                PatKind::Mac(ref mac) => mac.to_tokens(tokens),
             }
         }
     }
@@ -1687,15 +1824,15 @@ mod printing {
 
     impl ToTokens for Stmt {
         fn to_tokens(&self, tokens: &mut Tokens) {
-            match *self {
-                Stmt::Local(ref local) => local.to_tokens(tokens),
-                Stmt::Item(ref item) => item.to_tokens(tokens),
-                Stmt::Expr(ref expr) => expr.to_tokens(tokens),
-                Stmt::Semi(ref expr) => {
+            match self.node {
+                StmtKind::Local(ref local) => local.to_tokens(tokens),
+                StmtKind::Item(ref item) => item.to_tokens(tokens),
+                StmtKind::Expr(ref expr) => expr.to_tokens(tokens),
+                StmtKind::Semi(ref expr) => {
                     expr.to_tokens(tokens);
                     tokens.append(";");
                 }
-                Stmt::Mac(ref mac) => {
+                StmtKind::Mac(ref mac) => {
                     let (ref mac, style, ref attrs) = **mac;
                     tokens.append_all(attrs.outer());
                     mac.to_tokens(tokens);
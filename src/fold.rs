@@ -26,8 +26,8 @@ pub trait Folder {
     // being able to call out to the super default method
     // in an overridden default method.
 
-    fn fold_ident(&mut self, _ident: Ident) -> Ident {
-        noop_fold_ident(self, _ident)
+    fn fold_ident(&mut self, ident: Ident) -> Ident {
+        noop_fold_ident(self, ident)
     }
     fn fold_derive_input(&mut self, derive_input: DeriveInput) -> DeriveInput {
         noop_fold_derive_input(self, derive_input)
@@ -152,6 +152,55 @@ pub trait Folder {
     }
 }
 
+/// A Folder which moves every span by a fixed offset.
+///
+/// This rebases the spans of a fragment which was parsed on its own onto the
+/// larger source it was taken from, e.g. the body of a macro invocation:
+///
+/// ```rust,ignore
+/// let file = "fn f() { foo!(a + b) }";
+/// let expr = syn::parse_expr(&file[14..19]).unwrap();
+/// let expr = ShiftSpans::new(14).fold_expr(expr);
+/// assert_eq!(expr.span, Span { lo: 14, hi: 19 });
+/// ```
+pub struct ShiftSpans {
+    offset: usize,
+    skip_synthetic: bool,
+}
+
+impl ShiftSpans {
+    /// Moves every span by `offset`, including the empty span of a node
+    /// parsed at the very start of the fragment.
+    pub fn new(offset: usize) -> Self {
+        ShiftSpans {
+            offset: offset,
+            skip_synthetic: false,
+        }
+    }
+
+    /// Like `new`, but leaves `EMPTY_SPAN` as it is, for trees which mix
+    /// parsed nodes with synthetic ones. Parsed nodes whose span is empty and
+    /// at the start of the fragment are then left in place as well.
+    pub fn skipping_synthetic(offset: usize) -> Self {
+        ShiftSpans {
+            offset: offset,
+            skip_synthetic: true,
+        }
+    }
+}
+
+impl Folder for ShiftSpans {
+    fn fold_span(&mut self, span: Span) -> Span {
+        if self.skip_synthetic && span == EMPTY_SPAN {
+            return span;
+        }
+        Span {
+            lo: span.lo + self.offset,
+            hi: span.hi + self.offset,
+        }
+    }
+}
+
 trait LiftOnce<T, U> {
     type Output;
     fn lift<F>(self, f: F) -> Self::Output where F: FnOnce(T) -> U;
@@ -180,8 +229,9 @@ impl<T, U> LiftMut<T, U> for Vec<T> {
     }
 }
 
-pub fn noop_fold_ident<F: ?Sized + Folder>(_: &mut F, _ident: Ident) -> Ident {
-    _ident
+pub fn noop_fold_ident<F: ?Sized + Folder>(folder: &mut F, mut ident: Ident) -> Ident {
+    ident.span = ident.span.map(|span| folder.fold_span(span));
+    ident
 }
 
 pub fn noop_fold_derive_input<F: ?Sized + Folder>(folder: &mut F,
@@ -205,84 +255,111 @@ pub fn noop_fold_derive_input<F: ?Sized + Folder>(folder: &mut F,
     }
 }
 
-pub fn noop_fold_ty<F: ?Sized + Folder>(folder: &mut F, ty: Ty) -> Ty {
-    use Ty::*;
-    match ty {
-        Slice(inner) => Slice(inner.lift(|v| folder.fold_ty(v))),
-        Paren(inner) => Paren(inner.lift(|v| folder.fold_ty(v))),
-        Ptr(mutable_type) => {
-            let mutable_type_ = *mutable_type;
-            let MutTy { ty, mutability }: MutTy = mutable_type_;
-            Ptr(Box::new(MutTy {
-                ty: folder.fold_ty(ty),
-                mutability: mutability,
-            }))
-        }
-        Rptr(opt_lifetime, mutable_type) => {
-            let mutable_type_ = *mutable_type;
-            let MutTy { ty, mutability }: MutTy = mutable_type_;
-            Rptr(opt_lifetime.map(|l| folder.fold_lifetime(l)),
-                 Box::new(MutTy {
-                     ty: folder.fold_ty(ty),
-                     mutability: mutability,
-                 }))
-        }
-        Never => Never,
-        Infer => Infer,
-        Tup(tuple_element_types) => Tup(tuple_element_types.lift(|x| folder.fold_ty(x))),
-        BareFn(bare_fn) => {
-            let bf_ = *bare_fn;
-            let BareFnTy { unsafety, abi, lifetimes, inputs, output, variadic } = bf_;
-            BareFn(Box::new(BareFnTy {
-                unsafety: unsafety,
-                abi: abi,
-                lifetimes: lifetimes.lift(|l| folder.fold_lifetime_def(l)),
-                inputs: inputs.lift(|v| {
-                    BareFnArg {
-                        name: v.name.map(|n| folder.fold_ident(n)),
-                        ty: folder.fold_ty(v.ty),
-                    }
-                }),
-                output: folder.fold_fn_ret_ty(output),
-                variadic: variadic,
-            }))
-        }
-        Path(maybe_qself, path) => {
-            Path(maybe_qself.map(|v| noop_fold_qself(folder, v)),
-                 folder.fold_path(path))
-        }
-        Array(inner, len) => {
-            Array({
-                      inner.lift(|v| folder.fold_ty(v))
-                  },
-                  folder.fold_const_expr(len))
-        }
-        TraitObject(bounds) => TraitObject(bounds.lift(|v| folder.fold_ty_param_bound(v))),
-        ImplTrait(bounds) => ImplTrait(bounds.lift(|v| folder.fold_ty_param_bound(v))),
-        Mac(mac) => Mac(folder.fold_mac(mac)),
+pub fn noop_fold_ty<F: ?Sized + Folder>(folder: &mut F, Ty { node, span }: Ty) -> Ty {
+    use TyKind::*;
+    Ty {
+        node: match node {
+            Slice(inner) => Slice(inner.lift(|v| folder.fold_ty(v))),
+            Paren(inner) => Paren(inner.lift(|v| folder.fold_ty(v))),
+            Ptr(mutable_type) => {
+                let mutable_type_ = *mutable_type;
+                let MutTy { ty, mutability }: MutTy = mutable_type_;
+                Ptr(Box::new(MutTy {
+                    ty: folder.fold_ty(ty),
+                    mutability: mutability,
+                }))
+            }
+            Rptr(opt_lifetime, mutable_type) => {
+                let mutable_type_ = *mutable_type;
+                let MutTy { ty, mutability }: MutTy = mutable_type_;
+                Rptr(opt_lifetime.map(|l| folder.fold_lifetime(l)),
+                     Box::new(MutTy {
+                         ty: folder.fold_ty(ty),
+                         mutability: mutability,
+                     }))
+            }
+            Never => Never,
+            Infer => Infer,
+            Tup(tuple_element_types) => Tup(tuple_element_types.lift(|x| folder.fold_ty(x))),
+            BareFn(bare_fn) => {
+                let bf_ = *bare_fn;
+                let BareFnTy { unsafety, abi, lifetimes, inputs, output, rarrow_span, variadic } =
+                    bf_;
+                BareFn(Box::new(BareFnTy {
+                    unsafety: unsafety,
+                    abi: abi,
+                    lifetimes: lifetimes.lift(|l| folder.fold_lifetime_def(l)),
+                    inputs: inputs.lift(|v| {
+                        BareFnArg {
+                            name: v.name.map(|n| folder.fold_ident(n)),
+                            ty: folder.fold_ty(v.ty),
+                        }
+                    }),
+                    output: folder.fold_fn_ret_ty(output),
+                    rarrow_span: rarrow_span.map(|span| folder.fold_span(span)),
+                    variadic: variadic,
+                }))
+            }
+            Path(maybe_qself, path) => {
+                Path(maybe_qself.map(|v| noop_fold_qself(folder, v)),
+                     folder.fold_path(path))
+            }
+            Array(inner, len) => {
+                Array({
+                          inner.lift(|v| folder.fold_ty(v))
+                      },
+                      folder.fold_const_expr(len))
+            }
+            TraitObject(bounds, syntax) => {
+                TraitObject(bounds.lift(|v| folder.fold_ty_param_bound(v)), syntax)
+            }
+            ImplTrait(bounds) => ImplTrait(bounds.lift(|v| folder.fold_ty_param_bound(v))),
+            Mac(mac) => Mac(folder.fold_mac(mac)),
+        },
+        span: folder.fold_span(span),
     }
 }
 
-fn noop_fold_qself<F: ?Sized + Folder>(folder: &mut F, QSelf { ty, position }: QSelf) -> QSelf {
+fn noop_fold_qself<F: ?Sized + Folder>(folder: &mut F,
+                                       QSelf { ty, position, span }: QSelf)
+                                       -> QSelf {
     QSelf {
         ty: Box::new(folder.fold_ty(*(ty))),
         position: position,
+        span: folder.fold_span(span),
     }
 }
 
 pub fn noop_fold_generics<F: ?Sized + Folder>(folder: &mut F,
-                                     Generics { lifetimes, ty_params, where_clause }: Generics)
-                                     -> Generics {
+                                              Generics {
+                                                  lifetimes,
+                                                  params,
+                                                  where_clause,
+                                                  span,
+                                              }: Generics)
+                                              -> Generics {
     use WherePredicate::*;
     Generics {
         lifetimes: lifetimes.lift(|l| folder.fold_lifetime_def(l)),
-        ty_params: ty_params.lift(|ty| {
-            TyParam {
-                attrs: ty.attrs.lift(|a| folder.fold_attribute(a)),
-                ident: folder.fold_ident(ty.ident),
-                bounds: ty.bounds
-                    .lift(|ty_pb| folder.fold_ty_param_bound(ty_pb)),
-                default: ty.default.map(|v| folder.fold_ty(v)),
+        params: params.lift(|param| match param {
+            GenericParam::Type(ty) => {
+                GenericParam::Type(TyParam {
+                    attrs: ty.attrs.lift(|a| folder.fold_attribute(a)),
+                    ident: folder.fold_ident(ty.ident),
+                    bounds: ty.bounds
+                        .lift(|ty_pb| folder.fold_ty_param_bound(ty_pb)),
+                    default: ty.default.map(|v| folder.fold_ty(v)),
+                    span: folder.fold_span(ty.span),
+                })
+            }
+            GenericParam::Const(param) => {
+                GenericParam::Const(ConstParam {
+                    attrs: param.attrs.lift(|a| folder.fold_attribute(a)),
+                    ident: folder.fold_ident(param.ident),
+                    ty: folder.fold_ty(param.ty),
+                    default: param.default.map(|v| folder.fold_const_expr(v)),
+                    span: folder.fold_span(param.span),
+                })
             }
         }),
         where_clause: WhereClause {
@@ -295,6 +372,7 @@ pub fn noop_fold_generics<F: ?Sized + Folder>(folder: &mut F,
                             bounded_ty: folder.fold_ty(bound_predicate.bounded_ty),
                             bounds: bound_predicate.bounds
                                 .lift(|ty_pb| folder.fold_ty_param_bound(ty_pb)),
+                            span: folder.fold_span(bound_predicate.span),
                         })
                     }
                     RegionPredicate(region_predicate) => {
@@ -302,16 +380,20 @@ pub fn noop_fold_generics<F: ?Sized + Folder>(folder: &mut F,
                             lifetime: folder.fold_lifetime(region_predicate.lifetime),
                             bounds: region_predicate.bounds
                                 .lift(|b| folder.fold_lifetime(b)),
+                            span: folder.fold_span(region_predicate.span),
                         })
                     }
                     EqPredicate(eq_predicate) => {
                         EqPredicate(WhereEqPredicate {
                             lhs_ty: folder.fold_ty(eq_predicate.lhs_ty),
                             rhs_ty: folder.fold_ty(eq_predicate.rhs_ty),
+                            span: folder.fold_span(eq_predicate.span),
                         })
                     }
                 }),
+            where_span: where_clause.where_span.map(|span| folder.fold_span(span)),
         },
+        span: folder.fold_span(span),
     }
 }
 
@@ -352,17 +434,28 @@ pub fn noop_fold_field<F: ?Sized + Folder>(folder: &mut F, field: Field) -> Fiel
         vis: noop_fold_vis(folder, field.vis),
         attrs: field.attrs.lift(|a| folder.fold_attribute(a)),
         ty: folder.fold_ty(field.ty),
+        colon_span: field.colon_span.map(|span| folder.fold_span(span)),
+        span: folder.fold_span(field.span),
     }
 }
 
 pub fn noop_fold_variant<F: ?Sized + Folder>(folder: &mut F,
-                                    Variant { ident, attrs, data, discriminant }: Variant)
-                                    -> Variant {
+                                             Variant {
+                                                 ident,
+                                                 attrs,
+                                                 data,
+                                                 discriminant,
+                                                 discriminant_span,
+                                                 span,
+                                             }: Variant)
+                                             -> Variant {
     Variant {
         ident: folder.fold_ident(ident),
         attrs: attrs.lift(|v| folder.fold_attribute(v)),
         data: folder.fold_variant_data(data),
         discriminant: discriminant.map(|ce| folder.fold_const_expr(ce)),
+        discriminant_span: discriminant_span.map(|span| folder.fold_span(span)),
+        span: folder.fold_span(span),
     }
 }
 
@@ -371,28 +464,34 @@ pub fn noop_fold_lifetime<F: ?Sized + Folder>(folder: &mut F, _lifetime: Lifetim
 }
 
 pub fn noop_fold_lifetime_def<F: ?Sized + Folder>(folder: &mut F,
-                                         LifetimeDef { attrs, lifetime, bounds }: LifetimeDef)
+                                         LifetimeDef { attrs, lifetime, bounds, span }: LifetimeDef)
                                          -> LifetimeDef {
     LifetimeDef {
         attrs: attrs.lift(|x| folder.fold_attribute(x)),
         lifetime: folder.fold_lifetime(lifetime),
         bounds: bounds.lift(|l| folder.fold_lifetime(l)),
+        span: folder.fold_span(span),
     }
 }
 
-pub fn noop_fold_path<F: ?Sized + Folder>(folder: &mut F, Path { global, segments }: Path) -> Path {
+pub fn noop_fold_path<F: ?Sized + Folder>(folder: &mut F,
+                                          Path { global, segments, span }: Path)
+                                          -> Path {
     Path {
         global: global,
         segments: segments.lift(|s| folder.fold_path_segment(s)),
+        span: folder.fold_span(span),
     }
 }
 
 pub fn noop_fold_path_segment<F: ?Sized + Folder>(folder: &mut F,
-                                                  PathSegment { ident, parameters }: PathSegment)
+                                                  segment: PathSegment)
                                                   -> PathSegment {
+    let PathSegment { ident, parameters, span } = segment;
     PathSegment {
         ident: folder.fold_ident(ident),
         parameters: folder.fold_path_parameters(parameters),
+        span: folder.fold_span(span),
     }
 }
 
@@ -402,11 +501,15 @@ pub fn noop_fold_path_parameters<F: ?Sized + Folder>(folder: &mut F,
     use PathParameters::*;
     match path_parameters {
         AngleBracketed(d) => {
-            let AngleBracketedParameterData { lifetimes, types, bindings } = d;
+            let AngleBracketedParameterData { lifetimes, args, bindings, span } = d;
             AngleBracketed(AngleBracketedParameterData {
                 lifetimes: lifetimes.into_iter().map(|l| folder.fold_lifetime(l)).collect(),
-                types: types.lift(|ty| folder.fold_ty(ty)),
+                args: args.lift(|arg| match arg {
+                    GenericArg::Type(ty) => GenericArg::Type(folder.fold_ty(ty)),
+                    GenericArg::Const(c) => GenericArg::Const(folder.fold_const_expr(c)),
+                }),
                 bindings: bindings.lift(|tb| folder.fold_assoc_type_binding(tb)),
+                span: folder.fold_span(span),
             })
         }
         Parenthesized(d) => {
@@ -472,6 +575,7 @@ pub fn noop_fold_const_expr<F: ?Sized + Folder>(folder: &mut F, expr: ConstExpr)
                   i.lift(|e| folder.fold_const_expr(e)))
         }
         Paren(no_op) => Paren(no_op.lift(|e| folder.fold_const_expr(e))),
+        Block(block) => Block(block.lift(|e| folder.fold_const_expr(e))),
         Other(e) => Other(noop_fold_other_const_expr(folder, e)),
     }
 }
@@ -514,26 +618,35 @@ pub fn noop_fold_tt<F: ?Sized + Folder>(folder: &mut F, tt: TokenTree) -> TokenT
     }
 }
 
-pub fn noop_fold_meta_item<F: ?Sized + Folder>(folder: &mut F, mitem: MetaItem) -> MetaItem {
-    match mitem {
-        MetaItem::Word(id) => MetaItem::Word(folder.fold_ident(id)),
-        MetaItem::List(id, nested) => {
-            let nested = nested.lift(|n| noop_fold_nested_meta_item(folder, n));
-            MetaItem::List(folder.fold_ident(id), nested)
-        }
-        MetaItem::NameValue(id, lit) => MetaItem::NameValue(folder.fold_ident(id),
-                                                            folder.fold_lit(lit)),
+pub fn noop_fold_meta_item<F: ?Sized + Folder>(folder: &mut F,
+                                                MetaItem { node, span }: MetaItem)
+                                                -> MetaItem {
+    MetaItem {
+        node: match node {
+            MetaItemKind::Word(id) => MetaItemKind::Word(folder.fold_ident(id)),
+            MetaItemKind::List(id, nested) => {
+                let nested = nested.lift(|n| noop_fold_nested_meta_item(folder, n));
+                MetaItemKind::List(folder.fold_ident(id), nested)
+            }
+            MetaItemKind::NameValue(id, lit) => {
+                MetaItemKind::NameValue(folder.fold_ident(id), folder.fold_lit(lit))
+            }
+        },
+        span: folder.fold_span(span),
     }
 }
 
 pub fn noop_fold_nested_meta_item<F: ?Sized + Folder>(folder: &mut F,
-                                                      nested_mitem: NestedMetaItem)
+                                                      NestedMetaItem { node, span }: NestedMetaItem)
                                                       -> NestedMetaItem {
-    match nested_mitem {
-        NestedMetaItem::MetaItem(mitem) =>
-            NestedMetaItem::MetaItem(folder.fold_meta_item(mitem)),
-        NestedMetaItem::Literal(lit) =>
-            NestedMetaItem::Literal(folder.fold_lit(lit)),
+    NestedMetaItem {
+        node: match node {
+            NestedMetaItemKind::MetaItem(mitem) => {
+                NestedMetaItemKind::MetaItem(folder.fold_meta_item(mitem))
+            }
+            NestedMetaItemKind::Literal(lit) => NestedMetaItemKind::Literal(folder.fold_lit(lit)),
+        },
+        span: folder.fold_span(span),
     }
 }
 
@@ -562,16 +675,24 @@ pub fn noop_fold_crate<F: ?Sized + Folder>(folder: &mut F,
 
 #[cfg(feature = "full")]
 pub fn noop_fold_block<F: ?Sized + Folder>(folder: &mut F, block: Block) -> Block {
-    Block { stmts: block.stmts.lift(|s| folder.fold_stmt(s)) }
+    Block {
+        stmts: block.stmts.lift(|s| folder.fold_stmt(s)),
+        span: folder.fold_span(block.span),
+    }
 }
 
-fn noop_fold_vis<F: ?Sized + Folder>(folder: &mut F, vis: Visibility) -> Visibility {
-    use Visibility::*;
-    match vis {
-        Crate => Crate,
-        Inherited => Inherited,
-        Public => Public,
-        Restricted(path) => Restricted(path.lift(|p| folder.fold_path(p))),
+fn noop_fold_vis<F: ?Sized + Folder>(folder: &mut F,
+                                     Visibility { node, span }: Visibility)
+                                     -> Visibility {
+    use VisibilityKind::*;
+    Visibility {
+        node: match node {
+            Crate => Crate,
+            Inherited => Inherited,
+            Public => Public,
+            Restricted(path) => Restricted(path.lift(|p| folder.fold_path(p))),
+        },
+        span: span.map(|span| folder.fold_span(span)),
     }
 }
 
@@ -596,10 +717,11 @@ pub fn noop_fold_item<F: ?Sized + Folder>(folder: &mut F,
                 Const(ty.lift(|ty| folder.fold_ty(ty)),
                       expr.lift(|e| folder.fold_expr(e)))
             }
-            Fn(fn_decl, unsafety, constness, abi, generics, block) => {
+            Fn(fn_decl, unsafety, constness, asyncness, abi, generics, block) => {
                 Fn(fn_decl.lift(|v| folder.fold_fn_decl(v)),
                    unsafety,
                    constness,
+                   asyncness,
                    abi,
                    folder.fold_generics(generics),
                    block.lift(|v| folder.fold_block(v)))
@@ -643,6 +765,7 @@ pub fn noop_fold_item<F: ?Sized + Folder>(folder: &mut F,
                      impl_items.lift(|i| folder.fold_impl_item(i)))
             }
             Mac(mac) => Mac(folder.fold_mac(mac)),
+            Error(source) => Error(source),
         },
         span: folder.fold_span(span),
     }
@@ -718,21 +841,26 @@ pub fn noop_fold_expr<F: ?Sized + Folder>(folder: &mut F, Expr { node, attrs, sp
             }
             Match(e, arms) => {
                 Match(e.lift(|e| folder.fold_expr(e)),
-                      arms.lift(|Arm { attrs, pats, guard, body }: Arm| {
+                      arms.lift(|Arm { attrs, pats, guard, fat_arrow_span, body, span }: Arm| {
                     Arm {
                         attrs: attrs.lift(|a| folder.fold_attribute(a)),
                         pats: pats.lift(|p| folder.fold_pat(p)),
                         guard: guard.map(|v| v.lift(|e| folder.fold_expr(e))),
+                        fat_arrow_span: folder.fold_span(fat_arrow_span),
                         body: body.lift(|e| folder.fold_expr(e)),
+                        span: folder.fold_span(span),
                     }
                 }))
             }
-            Closure(capture_by, fn_decl, expr) => {
+            Closure(capture_by, asyncness, fn_decl, expr) => {
                 Closure(capture_by,
+                        asyncness,
                         fn_decl.lift(|v| folder.fold_fn_decl(v)),
                         expr.lift(|e| folder.fold_expr(e)))
             }
             Block(unsafety, block) => Block(unsafety, folder.fold_block(block)),
+            Async(capture_by, block) => Async(capture_by, folder.fold_block(block)),
+            Await(expr) => Await(expr.lift(|e| folder.fold_expr(e))),
             Assign(lhs, rhs) => {
                 Assign(lhs.lift(|e| folder.fold_expr(e)),
                        rhs.lift(|e| folder.fold_expr(e)))
@@ -815,56 +943,61 @@ pub fn noop_fold_foreign_item<F: ?Sized + Folder>(folder: &mut F,
 }
 
 #[cfg(feature = "full")]
-pub fn noop_fold_pat<F: ?Sized + Folder>(folder: &mut F, pat: Pat) -> Pat {
-    use Pat::*;
-    match pat {
-        Wild => Wild,
-        Ident(binding_mode, ident, pat) => {
-            Ident(binding_mode,
-                  folder.fold_ident(ident),
-                  pat.map(|p| p.lift(|p| folder.fold_pat(p))))
-        }
-        Struct(path, field_patterns, dots) => {
-            Struct(folder.fold_path(path),
-                   field_patterns.lift(|FieldPat { ident, pat, is_shorthand, attrs }: FieldPat| {
-                    FieldPat {
-                        ident: folder.fold_ident(ident),
-                        pat: pat.lift(|p| folder.fold_pat(p)),
-                        is_shorthand: is_shorthand,
-                        attrs: attrs.lift(|a| folder.fold_attribute(a)),
-                    }
-                }),
-                   dots)
-        }
-        TupleStruct(path, pats, len) => {
-            TupleStruct(folder.fold_path(path),
-                        pats.lift(|p| folder.fold_pat(p)),
-                        len)
-        }
-        Path(qself, path) => {
-            Path(qself.map(|v| noop_fold_qself(folder, v)),
-                 folder.fold_path(path))
-        }
-        Tuple(pats, len) => Tuple(pats.lift(|p| folder.fold_pat(p)), len),
-        Box(b) => Box(b.lift(|p| folder.fold_pat(p))),
-        Ref(b, mutability) => Ref(b.lift(|p| folder.fold_pat(p)), mutability),
-        Lit(expr) => Lit(expr.lift(|e| folder.fold_expr(e))),
-        Range(l, r) => {
-            Range(l.lift(|e| folder.fold_expr(e)),
-                  r.lift(|e| folder.fold_expr(e)))
-        }
-        Slice(lefts, pat, rights) => {
-            Slice(lefts.lift(|p| folder.fold_pat(p)),
-                  pat.map(|v| v.lift(|p| folder.fold_pat(p))),
-                  rights.lift(|p| folder.fold_pat(p)))
-        }
-        Mac(mac) => Mac(folder.fold_mac(mac)),
+pub fn noop_fold_pat<F: ?Sized + Folder>(folder: &mut F, Pat { node, span }: Pat) -> Pat {
+    use PatKind::*;
+    Pat {
+        node: match node {
+            Wild => Wild,
+            Ident(binding_mode, ident, pat) => {
+                Ident(binding_mode,
+                      folder.fold_ident(ident),
+                      pat.map(|p| p.lift(|p| folder.fold_pat(p))))
+            }
+            Struct(path, field_patterns, dots) => {
+                Struct(folder.fold_path(path),
+                       field_patterns.lift(|field_pat: FieldPat| {
+                        let FieldPat { ident, pat, is_shorthand, attrs, span } = field_pat;
+                        FieldPat {
+                            ident: folder.fold_ident(ident),
+                            pat: pat.lift(|p| folder.fold_pat(p)),
+                            is_shorthand: is_shorthand,
+                            attrs: attrs.lift(|a| folder.fold_attribute(a)),
+                            span: folder.fold_span(span),
+                        }
+                    }),
+                       dots)
+            }
+            TupleStruct(path, pats, len) => {
+                TupleStruct(folder.fold_path(path),
+                            pats.lift(|p| folder.fold_pat(p)),
+                            len)
+            }
+            Path(qself, path) => {
+                Path(qself.map(|v| noop_fold_qself(folder, v)),
+                     folder.fold_path(path))
+            }
+            Tuple(pats, len) => Tuple(pats.lift(|p| folder.fold_pat(p)), len),
+            Box(b) => Box(b.lift(|p| folder.fold_pat(p))),
+            Ref(b, mutability) => Ref(b.lift(|p| folder.fold_pat(p)), mutability),
+            Lit(expr) => Lit(expr.lift(|e| folder.fold_expr(e))),
+            Range(l, r) => {
+                Range(l.lift(|e| folder.fold_expr(e)),
+                      r.lift(|e| folder.fold_expr(e)))
+            }
+            Slice(lefts, pat, rights) => {
+                Slice(lefts.lift(|p| folder.fold_pat(p)),
+                      pat.map(|v| v.lift(|p| folder.fold_pat(p))),
+                      rights.lift(|p| folder.fold_pat(p)))
+            }
+            Mac(mac) => Mac(folder.fold_mac(mac)),
+        },
+        span: folder.fold_span(span),
     }
 }
 
 #[cfg(feature = "full")]
 pub fn noop_fold_fn_decl<F: ?Sized + Folder>(folder: &mut F,
-                                             FnDecl { inputs, output, variadic }: FnDecl)
+                                             FnDecl { inputs, output, rarrow_span, variadic }: FnDecl)
                                              -> FnDecl {
 
     FnDecl {
@@ -880,6 +1013,7 @@ pub fn noop_fold_fn_decl<F: ?Sized + Folder>(folder: &mut F,
             }
         }),
         output: folder.fold_fn_ret_ty(output),
+        rarrow_span: rarrow_span.map(|span| folder.fold_span(span)),
         variadic: variadic,
     }
 
@@ -902,8 +1036,9 @@ pub fn noop_fold_trait_item<F: ?Sized + Folder>(folder: &mut F,
                 Method(folder.fold_method_sig(sig),
                        block.map(|v| folder.fold_block(v)))
             }
-            Type(ty_pbs, ty) => {
-                Type(ty_pbs.lift(|v| folder.fold_ty_param_bound(v)),
+            Type(generics, ty_pbs, ty) => {
+                Type(folder.fold_generics(generics),
+                     ty_pbs.lift(|v| folder.fold_ty_param_bound(v)),
                      ty.map(|v| folder.fold_ty(v)))
             }
             Macro(mac) => Macro(folder.fold_mac(mac)),
@@ -917,6 +1052,7 @@ pub fn noop_fold_impl_item<F: ?Sized + Folder>(folder: &mut F,
                                                ImplItem { ident,
                                                           vis,
                                                           defaultness,
+                                                          defaultness_span,
                                                           attrs,
                                                           node,
                                                           span }: ImplItem)
@@ -926,11 +1062,12 @@ pub fn noop_fold_impl_item<F: ?Sized + Folder>(folder: &mut F,
         ident: folder.fold_ident(ident),
         vis: noop_fold_vis(folder, vis),
         defaultness: defaultness,
+        defaultness_span: defaultness_span.map(|span| folder.fold_span(span)),
         attrs: attrs.lift(|v| folder.fold_attribute(v)),
         node: match node {
             Const(ty, expr) => Const(folder.fold_ty(ty), folder.fold_expr(expr)),
             Method(sig, block) => Method(folder.fold_method_sig(sig), folder.fold_block(block)),
-            Type(ty) => Type(folder.fold_ty(ty)),
+            Type(generics, ty) => Type(folder.fold_generics(generics), folder.fold_ty(ty)),
             Macro(mac) => Macro(folder.fold_mac(mac)),
         },
         span: folder.fold_span(span),
@@ -938,10 +1075,11 @@ pub fn noop_fold_impl_item<F: ?Sized + Folder>(folder: &mut F,
 }
 
 #[cfg(feature = "full")]
-pub fn noop_fold_method_sig<F: ?Sized + Folder>(folder: &mut F, MethodSig{unsafety, constness, abi, decl, generics}:MethodSig) -> MethodSig {
+pub fn noop_fold_method_sig<F: ?Sized + Folder>(folder: &mut F, MethodSig{unsafety, constness, asyncness, abi, decl, generics}:MethodSig) -> MethodSig {
     MethodSig {
         unsafety: unsafety,
         constness: constness,
+        asyncness: asyncness,
         abi: abi,
         decl: folder.fold_fn_decl(decl),
         generics: folder.fold_generics(generics),
@@ -950,31 +1088,35 @@ pub fn noop_fold_method_sig<F: ?Sized + Folder>(folder: &mut F, MethodSig{unsafe
 }
 
 #[cfg(feature = "full")]
-pub fn noop_fold_stmt<F: ?Sized + Folder>(folder: &mut F, stmt: Stmt) -> Stmt {
-    use Stmt::*;
-    match stmt {
-        Local(local) => Local(local.lift(|l| folder.fold_local(l))),
-        Item(item) => Item(item.lift(|v| folder.fold_item(v))),
-        Expr(expr) => Expr(expr.lift(|v| folder.fold_expr(v))),
-        Semi(expr) => Semi(expr.lift(|v| folder.fold_expr(v))),
-        Mac(mac_stmt) => {
-            Mac(mac_stmt.lift(|(mac, style, attrs)| {
-                (folder.fold_mac(mac), style, attrs.lift(|a| folder.fold_attribute(a)))
-            }))
-        }
+pub fn noop_fold_stmt<F: ?Sized + Folder>(folder: &mut F, Stmt { node, span }: Stmt) -> Stmt {
+    use StmtKind::*;
+    Stmt {
+        node: match node {
+            Local(local) => Local(local.lift(|l| folder.fold_local(l))),
+            Item(item) => Item(item.lift(|v| folder.fold_item(v))),
+            Expr(expr) => Expr(expr.lift(|v| folder.fold_expr(v))),
+            Semi(expr) => Semi(expr.lift(|v| folder.fold_expr(v))),
+            Mac(mac_stmt) => {
+                Mac(mac_stmt.lift(|(mac, style, attrs)| {
+                    (folder.fold_mac(mac), style, attrs.lift(|a| folder.fold_attribute(a)))
+                }))
+            }
+        },
+        span: folder.fold_span(span),
     }
 
 }
 
 #[cfg(feature = "full")]
 pub fn noop_fold_local<F: ?Sized + Folder>(folder: &mut F,
-                                           Local { pat, ty, init, attrs }: Local)
+                                           Local { pat, ty, init, attrs, span }: Local)
                                            -> Local {
     Local {
         pat: pat.lift(|v| folder.fold_pat(v)),
         ty: ty.map(|v| v.lift(|t| folder.fold_ty(t))),
         init: init.map(|v| v.lift(|e| folder.fold_expr(e))),
         attrs: attrs.lift(|a| folder.fold_attribute(a)),
+        span: folder.fold_span(span),
     }
 }
 
@@ -986,12 +1128,7 @@ pub fn noop_fold_view_path<F: ?Sized + Folder>(folder: &mut F, view_path: ViewPa
         Glob(path) => Glob(folder.fold_path(path)),
         List(path, items) => {
             List(folder.fold_path(path),
-                 items.lift(|PathListItem { name, rename }: PathListItem| {
-                     PathListItem {
-                         name: folder.fold_ident(name),
-                         rename: rename.map(|i| folder.fold_ident(i)),
-                     }
-                 }))
+                 items.lift(|item| folder.fold_view_path(item)))
         }
     }
 }
@@ -1,12 +1,18 @@
 use super::*;
 
-/// Represents lifetimes and type parameters attached to a declaration
-/// of a function, enum, trait, etc.
+/// Represents lifetimes, type parameters and const parameters attached to a
+/// declaration of a function, enum, trait, etc.
 #[derive(Debug, Clone, Eq, PartialEq, Default, Hash)]
 pub struct Generics {
     pub lifetimes: Vec<LifetimeDef>,
-    pub ty_params: Vec<TyParam>,
+    /// The type and const parameters in the order they were written. The
+    /// lifetimes come before them.
+    pub params: Vec<GenericParam>,
     pub where_clause: WhereClause,
+    /// The span from `<` to `>`, or an empty span just after the preceding
+    /// token if there are no angle brackets. This does not cover the where
+    /// clause.
+    pub span: Span,
 }
 
 #[cfg(feature = "printing")]
@@ -60,6 +66,8 @@ impl<'a> TyGenerics<'a> {
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Lifetime {
+    /// The lifetime name including the apostrophe. Its span, if any, covers
+    /// the whole lifetime.
     pub ident: Ident,
 }
 
@@ -81,6 +89,8 @@ pub struct LifetimeDef {
     pub attrs: Vec<Attribute>,
     pub lifetime: Lifetime,
     pub bounds: Vec<Lifetime>,
+    /// The span from the first attribute or the lifetime to the last bound
+    pub span: Span,
 }
 
 impl LifetimeDef {
@@ -89,16 +99,39 @@ impl LifetimeDef {
             attrs: Vec::new(),
             lifetime: Lifetime::new(t),
             bounds: Vec::new(),
+            span: EMPTY_SPAN,
         }
     }
 }
 
+/// A type or const parameter.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum GenericParam {
+    Type(TyParam),
+    Const(ConstParam),
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct TyParam {
     pub attrs: Vec<Attribute>,
     pub ident: Ident,
     pub bounds: Vec<TyParamBound>,
     pub default: Option<Ty>,
+    /// The span from the first attribute or the identifier to the end of the
+    /// default, if any
+    pub span: Span,
+}
+
+/// A const generic parameter, e.g. `const N: usize = 3`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ConstParam {
+    pub attrs: Vec<Attribute>,
+    pub ident: Ident,
+    pub ty: Ty,
+    pub default: Option<ConstExpr>,
+    /// The span from the first attribute or the `const` keyword to the end of
+    /// the default, if any
+    pub span: Span,
 }
 
 /// The AST represents all type param bounds as types.
@@ -123,11 +156,17 @@ pub enum TraitBoundModifier {
 #[derive(Debug, Clone, Eq, PartialEq, Default, Hash)]
 pub struct WhereClause {
     pub predicates: Vec<WherePredicate>,
+    /// The span of the `where` keyword, or `None` if there is no `where`
+    /// clause or it was not produced by the parser.
+    pub where_span: Option<Span>,
 }
 
 impl WhereClause {
     pub fn none() -> Self {
-        WhereClause { predicates: Vec::new() }
+        WhereClause {
+            predicates: Vec::new(),
+            where_span: None,
+        }
     }
 }
 
@@ -153,6 +192,7 @@ pub struct WhereBoundPredicate {
     pub bounded_ty: Ty,
     /// Trait and lifetime bounds (`Clone+Send+'static`)
     pub bounds: Vec<TyParamBound>,
+    pub span: Span,
 }
 
 /// A lifetime predicate.
@@ -162,6 +202,7 @@ pub struct WhereBoundPredicate {
 pub struct WhereRegionPredicate {
     pub lifetime: Lifetime,
     pub bounds: Vec<Lifetime>,
+    pub span: Span,
 }
 
 /// An equality predicate (unsupported).
@@ -171,52 +212,68 @@ pub struct WhereRegionPredicate {
 pub struct WhereEqPredicate {
     pub lhs_ty: Ty,
     pub rhs_ty: Ty,
+    pub span: Span,
 }
 
 #[cfg(feature = "parsing")]
 pub mod parsing {
     use super::*;
     use attr::parsing::outer_attr;
+    use constant::parsing::const_arg;
     use ident::parsing::ident;
-    use ty::parsing::{ty, poly_trait_ref};
+    use ty::parsing::{ty, path, poly_trait_ref};
+    use synom::{IResult, ParseState};
 
     named!(pub generics -> Generics, map!(
         alt!(
-            do_parse!(
+            spanned!(do_parse!(
                 punct!("<") >>
                 lifetimes: separated_list!(punct!(","), lifetime_def) >>
-                ty_params: opt_vec!(preceded!(
+                params: opt_vec!(preceded!(
                     cond!(!lifetimes.is_empty(), punct!(",")),
-                    separated_nonempty_list!(punct!(","), ty_param)
+                    separated_nonempty_list!(punct!(","), generic_param)
                 )) >>
-                cond!(!lifetimes.is_empty() || !ty_params.is_empty(), option!(punct!(","))) >>
+                cond!(!lifetimes.is_empty() || !params.is_empty(), option!(punct!(","))) >>
                 punct!(">") >>
-                (lifetimes, ty_params)
-            )
+                (lifetimes, params)
+            ))
             |
-            epsilon!() => { |_| (Vec::new(), Vec::new()) }
+            empty_span => { |span| Spanned { node: Default::default(), span: span } }
         ),
-        |(lifetimes, ty_params)| Generics {
+        |Spanned { node: (lifetimes, params), span }| Generics {
             lifetimes: lifetimes,
-            ty_params: ty_params,
+            params: params,
             where_clause: Default::default(),
+            span: span,
         }
     ));
 
-    named!(pub lifetime -> Lifetime, preceded!(
-        punct!("'"),
-        alt!(
-            map!(ident, |id| Lifetime {
-                ident: format!("'{}", id).into(),
-            })
-            |
-            map!(keyword!("static"), |_| Lifetime {
-                ident: "'static".into(),
-            })
-        )
+    /// An empty span at the current position, before any whitespace.
+    fn empty_span(input: ParseState) -> IResult<ParseState, Span> {
+        let span = Span {
+            lo: input.idx(),
+            hi: input.idx(),
+        };
+        IResult::Done(input, span)
+    }
+
+    named!(pub lifetime -> Lifetime, map!(
+        spanned!(preceded!(
+            punct!("'"),
+            terminated!(
+                alt!(
+                    map!(ident, |id| format!("'{}", id))
+                    |
+                    map!(keyword!("static"), |_| "'static".to_owned())
+                ),
+                // Not the start of a char literal like `'a'`
+                not!(tag!("'"))
+            )
+        )),
+        |name: Spanned<String>| Lifetime { ident: Ident::with_span(name.node, name.span) }
     ));
 
-    named!(pub lifetime_def -> LifetimeDef, do_parse!(
+    named!(pub lifetime_def -> LifetimeDef, add_span!(do_parse!(
         attrs: many0!(outer_attr) >>
         life: lifetime >>
         bounds: opt_vec!(preceded!(
@@ -227,8 +284,9 @@ pub mod parsing {
             attrs: attrs,
             lifetime: life,
             bounds: bounds,
+            span: DUMMY_SPAN,
         })
-    ));
+    )));
 
     named!(pub bound_lifetimes -> Vec<LifetimeDef>, opt_vec!(do_parse!(
         keyword!("for") >>
@@ -238,7 +296,13 @@ pub mod parsing {
         (lifetimes)
     )));
 
-    named!(ty_param -> TyParam, do_parse!(
+    named!(generic_param -> GenericParam, alt!(
+        const_param => { GenericParam::Const }
+        |
+        ty_param => { GenericParam::Type }
+    ));
+
+    named!(ty_param -> TyParam, add_span!(do_parse!(
         attrs: many0!(outer_attr) >>
         id: ident >>
         bounds: opt_vec!(preceded!(
@@ -254,8 +318,32 @@ pub mod parsing {
             ident: id,
             bounds: bounds,
             default: default,
+            span: DUMMY_SPAN,
         })
-    ));
+    )));
+
+    named!(const_param -> ConstParam, add_span!(do_parse!(
+        attrs: many0!(outer_attr) >>
+        keyword!("const") >>
+        id: ident >>
+        punct!(":") >>
+        ty: ty >>
+        default: option!(preceded!(
+            punct!("="),
+            alt!(
+                const_arg
+                |
+                map!(path, ConstExpr::Path)
+            )
+        )) >>
+        (ConstParam {
+            attrs: attrs,
+            ident: id,
+            ty: ty,
+            default: default,
+            span: DUMMY_SPAN,
+        })
+    )));
 
     named!(pub ty_param_bound -> TyParamBound, alt!(
         preceded!(punct!("?"), poly_trait_ref) => {
@@ -271,39 +359,44 @@ pub mod parsing {
 
     named!(pub where_clause -> WhereClause, alt!(
         do_parse!(
-            keyword!("where") >>
+            where_kw: spanned!(keyword!("where")) >>
             predicates: separated_nonempty_list!(punct!(","), where_predicate) >>
             option!(punct!(",")) >>
-            (WhereClause { predicates: predicates })
+            (WhereClause {
+                predicates: predicates,
+                where_span: Some(where_kw.span),
+            })
         )
         |
         epsilon!() => { |_| Default::default() }
     ));
 
     named!(where_predicate -> WherePredicate, alt!(
-        do_parse!(
+        add_span!(do_parse!(
             ident: lifetime >>
             bounds: opt_vec!(preceded!(
                 punct!(":"),
                 separated_list!(punct!("+"), lifetime)
             )) >>
-            (WherePredicate::RegionPredicate(WhereRegionPredicate {
+            (WhereRegionPredicate {
                 lifetime: ident,
                 bounds: bounds,
-            }))
-        )
+                span: DUMMY_SPAN,
+            })
+        )) => { WherePredicate::RegionPredicate }
         |
-        do_parse!(
+        add_span!(do_parse!(
             bound_lifetimes: bound_lifetimes >>
             bounded_ty: ty >>
             punct!(":") >>
             bounds: separated_nonempty_list!(punct!("+"), ty_param_bound) >>
-            (WherePredicate::BoundPredicate(WhereBoundPredicate {
+            (WhereBoundPredicate {
                 bound_lifetimes: bound_lifetimes,
                 bounded_ty: bounded_ty,
                 bounds: bounds,
-            }))
-        )
+                span: DUMMY_SPAN,
+            })
+        )) => { WherePredicate::BoundPredicate }
     ));
 }
 
@@ -316,14 +409,14 @@ mod printing {
     impl ToTokens for Generics {
         fn to_tokens(&self, tokens: &mut Tokens) {
             let has_lifetimes = !self.lifetimes.is_empty();
-            let has_ty_params = !self.ty_params.is_empty();
-            if has_lifetimes || has_ty_params {
+            let has_params = !self.params.is_empty();
+            if has_lifetimes || has_params {
                 tokens.append("<");
                 tokens.append_separated(&self.lifetimes, ",");
-                if has_lifetimes && has_ty_params {
+                if has_lifetimes && has_params {
                     tokens.append(",");
                 }
-                tokens.append_separated(&self.ty_params, ",");
+                tokens.append_separated(&self.params, ",");
                 tokens.append(">");
             }
         }
@@ -332,20 +425,31 @@ mod printing {
     impl<'a> ToTokens for ImplGenerics<'a> {
         fn to_tokens(&self, tokens: &mut Tokens) {
             let has_lifetimes = !self.0.lifetimes.is_empty();
-            let has_ty_params = !self.0.ty_params.is_empty();
-            if has_lifetimes || has_ty_params {
+            let has_params = !self.0.params.is_empty();
+            if has_lifetimes || has_params {
                 tokens.append("<");
                 tokens.append_separated(&self.0.lifetimes, ",");
-                // Leave off the type parameter defaults
-                for (i, ty_param) in self.0.ty_params.iter().enumerate() {
+                // Leave off the parameter defaults
+                for (i, param) in self.0.params.iter().enumerate() {
                     if i > 0 || has_lifetimes {
                         tokens.append(",");
                     }
-                    tokens.append_all(ty_param.attrs.outer());
-                    ty_param.ident.to_tokens(tokens);
-                    if !ty_param.bounds.is_empty() {
-                        tokens.append(":");
-                        tokens.append_separated(&ty_param.bounds, "+");
+                    match *param {
+                        GenericParam::Type(ref ty_param) => {
+                            tokens.append_all(ty_param.attrs.outer());
+                            ty_param.ident.to_tokens(tokens);
+                            if !ty_param.bounds.is_empty() {
+                                tokens.append(":");
+                                tokens.append_separated(&ty_param.bounds, "+");
+                            }
+                        }
+                        GenericParam::Const(ref const_param) => {
+                            tokens.append_all(const_param.attrs.outer());
+                            tokens.append("const");
+                            const_param.ident.to_tokens(tokens);
+                            tokens.append(":");
+                            const_param.ty.to_tokens(tokens);
+                        }
                     }
                 }
                 tokens.append(">");
@@ -356,18 +460,22 @@ mod printing {
     impl<'a> ToTokens for TyGenerics<'a> {
         fn to_tokens(&self, tokens: &mut Tokens) {
             let has_lifetimes = !self.0.lifetimes.is_empty();
-            let has_ty_params = !self.0.ty_params.is_empty();
-            if has_lifetimes || has_ty_params {
+            let has_params = !self.0.params.is_empty();
+            if has_lifetimes || has_params {
                 tokens.append("<");
                 // Leave off the lifetime bounds and attributes
                 let lifetimes = self.0.lifetimes.iter().map(|ld| &ld.lifetime);
                 tokens.append_separated(lifetimes, ",");
-                if has_lifetimes && has_ty_params {
+                if has_lifetimes && has_params {
                     tokens.append(",");
                 }
-                // Leave off the type parameter bounds, defaults, and attributes
-                let ty_params = self.0.ty_params.iter().map(|tp| &tp.ident);
-                tokens.append_separated(ty_params, ",");
+                // Leave off the parameter bounds, types, defaults, and
+                // attributes
+                let params = self.0.params.iter().map(|param| match *param {
+                    GenericParam::Type(ref ty_param) => &ty_param.ident,
+                    GenericParam::Const(ref const_param) => &const_param.ident,
+                });
+                tokens.append_separated(params, ",");
                 tokens.append(">");
             }
         }
@@ -376,8 +484,8 @@ mod printing {
     impl<'a> ToTokens for Turbofish<'a> {
         fn to_tokens(&self, tokens: &mut Tokens) {
             let has_lifetimes = !self.0.lifetimes.is_empty();
-            let has_ty_params = !self.0.ty_params.is_empty();
-            if has_lifetimes || has_ty_params {
+            let has_params = !self.0.params.is_empty();
+            if has_lifetimes || has_params {
                 tokens.append("::");
                 TyGenerics(self.0).to_tokens(tokens);
             }
@@ -401,6 +509,15 @@ mod printing {
         }
     }
 
+    impl ToTokens for GenericParam {
+        fn to_tokens(&self, tokens: &mut Tokens) {
+            match *self {
+                GenericParam::Type(ref ty_param) => ty_param.to_tokens(tokens),
+                GenericParam::Const(ref const_param) => const_param.to_tokens(tokens),
+            }
+        }
+    }
+
     impl ToTokens for TyParam {
         fn to_tokens(&self, tokens: &mut Tokens) {
             tokens.append_all(self.attrs.outer());
@@ -416,6 +533,20 @@ mod printing {
         }
     }
 
+    impl ToTokens for ConstParam {
+        fn to_tokens(&self, tokens: &mut Tokens) {
+            tokens.append_all(self.attrs.outer());
+            tokens.append("const");
+            self.ident.to_tokens(tokens);
+            tokens.append(":");
+            self.ty.to_tokens(tokens);
+            if let Some(ref default) = self.default {
+                tokens.append("=");
+                default.to_tokens(tokens);
+            }
+        }
+    }
+
     impl ToTokens for TyParamBound {
         fn to_tokens(&self, tokens: &mut Tokens) {
             match *self {
@@ -0,0 +1,385 @@
+//! Uniform access to the source location of syntax tree nodes.
+
+use super::*;
+
+/// A syntax tree node which can report the region of source it covers.
+///
+/// Nodes which store a span report it directly. Nodes which don't store one
+/// report the smallest span covering all of their children, so e.g. the span
+/// of a `FnDecl` runs from the start of its first argument to the end of its
+/// return type, not including the parentheses.
+pub trait HasSpan {
+    /// The span of the node, or `None` if neither the node nor any of its
+    /// children were produced by the parser.
+    fn span(&self) -> Option<Span>;
+}
+
+/// The smallest span covering all of the given spans.
+fn join(spans: &[Option<Span>]) -> Option<Span> {
+    spans.iter().fold(None, |joined, &span| {
+        match (joined, span) {
+            (Some(joined), Some(span)) => Some(joined.join(span)),
+            (joined, span) => joined.or(span),
+        }
+    })
+}
+
+impl HasSpan for Span {
+    fn span(&self) -> Option<Span> {
+        if *self == EMPTY_SPAN {
+            None
+        } else {
+            Some(*self)
+        }
+    }
+}
+
+impl<T: HasSpan> HasSpan for Option<T> {
+    fn span(&self) -> Option<Span> {
+        self.as_ref().and_then(HasSpan::span)
+    }
+}
+
+impl<T: ?Sized + HasSpan> HasSpan for Box<T> {
+    fn span(&self) -> Option<Span> {
+        (**self).span()
+    }
+}
+
+impl<T: HasSpan> HasSpan for [T] {
+    fn span(&self) -> Option<Span> {
+        join(&self.iter().map(HasSpan::span).collect::<Vec<_>>())
+    }
+}
+
+impl<T: HasSpan> HasSpan for Vec<T> {
+    fn span(&self) -> Option<Span> {
+        self[..].span()
+    }
+}
+
+/// Nodes which store their own span.
+macro_rules! stored_span {
+    ($($ty:ty,)*) => {
+        $(
+            impl HasSpan for $ty {
+                fn span(&self) -> Option<Span> {
+                    self.span.span()
+                }
+            }
+        )*
+    };
+}
+
+/// Nodes which never carry a location, such as keywords and operators.
+macro_rules! no_span {
+    ($($ty:ty,)*) => {
+        $(
+            impl HasSpan for $ty {
+                fn span(&self) -> Option<Span> {
+                    None
+                }
+            }
+        )*
+    };
+}
+
+stored_span! {
+    Ident,
+    Attribute,
+    MetaItem,
+    NestedMetaItem,
+    Variant,
+    Field,
+    Visibility,
+    DeriveInput,
+    Generics,
+    LifetimeDef,
+    TyParam,
+    ConstParam,
+    WhereBoundPredicate,
+    WhereRegionPredicate,
+    WhereEqPredicate,
+    Lit,
+    Ty,
+    Path,
+    PathSegment,
+    AngleBracketedParameterData,
+    QSelf,
+}
+
+#[cfg(feature = "full")]
+stored_span! {
+    Expr,
+    Block,
+    Stmt,
+    Local,
+    Pat,
+    Arm,
+    FieldPat,
+    Item,
+    ForeignItem,
+    TraitItem,
+    ImplItem,
+}
+
+no_span! {
+    AttrStyle,
+    TraitBoundModifier,
+    LitKind,
+    StrStyle,
+    IntTy,
+    FloatTy,
+    BinOpToken,
+    DelimToken,
+    BinOp,
+    UnOp,
+    Mutability,
+    Unsafety,
+    Abi,
+}
+
+#[cfg(feature = "full")]
+no_span! {
+    MacStmtStyle,
+    CaptureBy,
+    RangeLimits,
+    BindingMode,
+    Constness,
+    Asyncness,
+    Defaultness,
+    ImplPolarity,
+}
+
+impl HasSpan for ConstExpr {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            ConstExpr::Call(ref func, ref args) => join(&[func.span(), args.span()]),
+            ConstExpr::Binary(_, ref lhs, ref rhs) => join(&[lhs.span(), rhs.span()]),
+            ConstExpr::Unary(_, ref operand) => operand.span(),
+            ConstExpr::Lit(ref lit) => lit.span(),
+            ConstExpr::Cast(ref expr, ref ty) => join(&[expr.span(), ty.span()]),
+            ConstExpr::Path(ref path) => path.span(),
+            ConstExpr::Index(ref expr, ref index) => join(&[expr.span(), index.span()]),
+            ConstExpr::Paren(ref expr) |
+            ConstExpr::Block(ref expr) => expr.span(),
+            ConstExpr::Other(ref other) => other.span(),
+        }
+    }
+}
+
+#[cfg(not(feature = "full"))]
+impl HasSpan for ::constant::Other {
+    fn span(&self) -> Option<Span> {
+        None
+    }
+}
+
+impl HasSpan for VariantData {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            VariantData::Struct(ref fields) |
+            VariantData::Tuple(ref fields) => fields.span(),
+            VariantData::Unit => None,
+        }
+    }
+}
+
+impl HasSpan for Body {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            Body::Enum(ref variants) => variants.span(),
+            Body::Struct(ref data) => data.span(),
+        }
+    }
+}
+
+impl HasSpan for Lifetime {
+    fn span(&self) -> Option<Span> {
+        self.ident.span()
+    }
+}
+
+impl HasSpan for TyParamBound {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            TyParamBound::Trait(ref poly_trait_ref, _) => poly_trait_ref.span(),
+            TyParamBound::Region(ref lifetime) => lifetime.span(),
+        }
+    }
+}
+
+impl HasSpan for GenericParam {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            GenericParam::Type(ref param) => param.span(),
+            GenericParam::Const(ref param) => param.span(),
+        }
+    }
+}
+
+impl HasSpan for GenericArg {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            GenericArg::Type(ref ty) => ty.span(),
+            GenericArg::Const(ref expr) => expr.span(),
+        }
+    }
+}
+
+impl HasSpan for WhereClause {
+    fn span(&self) -> Option<Span> {
+        join(&[self.where_span, self.predicates.span()])
+    }
+}
+
+impl HasSpan for WherePredicate {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            WherePredicate::BoundPredicate(ref predicate) => predicate.span(),
+            WherePredicate::RegionPredicate(ref predicate) => predicate.span(),
+            WherePredicate::EqPredicate(ref predicate) => predicate.span(),
+        }
+    }
+}
+
+impl HasSpan for Mac {
+    fn span(&self) -> Option<Span> {
+        join(&[self.path.span(), self.tts.span()])
+    }
+}
+
+impl HasSpan for TokenTree {
+    fn span(&self) -> Option<Span> {
+        TokenTree::span(self).span()
+    }
+}
+
+impl HasSpan for Delimited {
+    fn span(&self) -> Option<Span> {
+        self.tts.span()
+    }
+}
+
+impl HasSpan for Token {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            Token::Literal(ref lit) => lit.span(),
+            Token::Ident(ref ident) |
+            Token::Lifetime(ref ident) => ident.span(),
+            _ => None,
+        }
+    }
+}
+
+impl HasSpan for MutTy {
+    fn span(&self) -> Option<Span> {
+        self.ty.span()
+    }
+}
+
+impl HasSpan for PathParameters {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            PathParameters::AngleBracketed(ref data) => data.span(),
+            PathParameters::Parenthesized(ref data) => data.span(),
+        }
+    }
+}
+
+impl HasSpan for TypeBinding {
+    fn span(&self) -> Option<Span> {
+        join(&[self.ident.span(), self.ty.span()])
+    }
+}
+
+impl HasSpan for ParenthesizedParameterData {
+    fn span(&self) -> Option<Span> {
+        join(&[self.inputs.span(), self.output.span()])
+    }
+}
+
+impl HasSpan for PolyTraitRef {
+    fn span(&self) -> Option<Span> {
+        join(&[self.bound_lifetimes.span(), self.trait_ref.span()])
+    }
+}
+
+impl HasSpan for BareFnTy {
+    fn span(&self) -> Option<Span> {
+        join(&[self.lifetimes.span(), self.inputs.span(), self.output.span()])
+    }
+}
+
+impl HasSpan for BareFnArg {
+    fn span(&self) -> Option<Span> {
+        join(&[self.name.span(), self.ty.span()])
+    }
+}
+
+impl HasSpan for FunctionRetTy {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            FunctionRetTy::Default => None,
+            FunctionRetTy::Ty(ref ty) => ty.span(),
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl HasSpan for FieldValue {
+    fn span(&self) -> Option<Span> {
+        join(&[self.attrs.span(), self.ident.span(), self.expr.span()])
+    }
+}
+
+#[cfg(feature = "full")]
+impl HasSpan for ViewPath {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            ViewPath::Simple(ref path, ref rename) => join(&[path.span(), rename.span()]),
+            ViewPath::Glob(ref path) => path.span(),
+            ViewPath::List(ref path, ref items) => join(&[path.span(), items.span()]),
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl HasSpan for ForeignMod {
+    fn span(&self) -> Option<Span> {
+        self.items.span()
+    }
+}
+
+#[cfg(feature = "full")]
+impl HasSpan for MethodSig {
+    fn span(&self) -> Option<Span> {
+        join(&[self.generics.span(), self.decl.span()])
+    }
+}
+
+#[cfg(feature = "full")]
+impl HasSpan for FnDecl {
+    fn span(&self) -> Option<Span> {
+        join(&[self.inputs.span(), self.output.span()])
+    }
+}
+
+#[cfg(feature = "full")]
+impl HasSpan for FnArg {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            FnArg::SelfRef(ref lifetime, _) => lifetime.span(),
+            FnArg::SelfValue(_) => None,
+            FnArg::Captured(ref pat, ref ty) => join(&[pat.span(), ty.span()]),
+            FnArg::Ignored(ref ty) => ty.span(),
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl HasSpan for Crate {
+    fn span(&self) -> Option<Span> {
+        join(&[self.attrs.span(), self.items.span()])
+    }
+}
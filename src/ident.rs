@@ -1,48 +1,76 @@
 use std::borrow::Cow;
 use std::fmt::{self, Display};
-
-#[derive(Debug, Clone, Eq, Hash)]
-pub struct Ident(String);
+use std::hash::{Hash, Hasher};
+
+use Span;
+
+/// An identifier, optionally carrying the location it was parsed from.
+///
+/// Comparison and hashing only consider the identifier itself, so an `Ident`
+/// produced by the parser compares equal to one built with `Ident::new`.
+#[derive(Debug, Clone, Eq)]
+pub struct Ident {
+    sym: String,
+    /// The source location of this identifier, or `None` if it was not
+    /// produced by the parser.
+    pub span: Option<Span>,
+}
 
 impl Ident {
     pub fn new<T: Into<Ident>>(t: T) -> Self {
         t.into()
     }
+
+    pub fn with_span<T: Into<Ident>>(t: T, span: Span) -> Self {
+        Ident { span: Some(span), ..t.into() }
+    }
 }
 
 impl<'a> From<&'a str> for Ident {
     fn from(s: &str) -> Self {
-        Ident(s.to_owned())
+        Ident {
+            sym: s.to_owned(),
+            span: None,
+        }
     }
 }
 
 impl<'a> From<Cow<'a, str>> for Ident {
     fn from(s: Cow<'a, str>) -> Self {
-        Ident(s.into_owned())
+        Ident {
+            sym: s.into_owned(),
+            span: None,
+        }
     }
 }
 
 impl From<String> for Ident {
     fn from(s: String) -> Self {
-        Ident(s)
+        Ident {
+            sym: s,
+            span: None,
+        }
     }
 }
 
 impl From<usize> for Ident {
     fn from(u: usize) -> Self {
-        Ident(u.to_string())
+        Ident {
+            sym: u.to_string(),
+            span: None,
+        }
     }
 }
 
 impl AsRef<str> for Ident {
     fn as_ref(&self) -> &str {
-        &self.0
+        &self.sym
     }
 }
 
 impl Display for Ident {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        self.0.fmt(formatter)
+        self.sym.fmt(formatter)
     }
 }
 
@@ -50,21 +78,38 @@ impl<T: ?Sized> PartialEq<T> for Ident
     where T: AsRef<str>
 {
     fn eq(&self, other: &T) -> bool {
-        self.0 == other.as_ref()
+        self.sym == other.as_ref()
+    }
+}
+
+impl Hash for Ident {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sym.hash(state)
     }
 }
 
 #[cfg(feature = "parsing")]
 pub mod parsing {
     use super::*;
-    use synom::{IResult, ParseState};
+    use synom::{Expected, IResult, ParseState};
     use synom::space::skip_whitespace;
     use unicode_xid::UnicodeXID;
 
+    fn located(input: ParseState, len: usize) -> Ident {
+        let span = Span {
+            lo: input.idx(),
+            hi: input.idx() + len,
+        };
+        Ident::with_span(input.until(len), span)
+    }
+
     pub fn ident(input: ParseState) -> IResult<ParseState, Ident> {
         let (rest, id) = match word(input) {
             IResult::Done(rest, id) => (rest, id),
-            IResult::Error => return IResult::Error,
+            IResult::Error => {
+                skip_whitespace(input).expected(Expected::Named("identifier"));
+                return IResult::Error;
+            }
         };
 
         match id.as_ref() {
@@ -75,7 +120,10 @@ pub mod parsing {
             "mut" | "offsetof" | "override" | "priv" | "proc" | "pub" | "pure" | "ref" |
             "return" | "Self" | "self" | "sizeof" | "static" | "struct" | "super" | "trait" |
             "true" | "type" | "typeof" | "unsafe" | "unsized" | "use" | "virtual" | "where" |
-            "while" | "yield" => IResult::Error,
+            "while" | "yield" => {
+                skip_whitespace(input).expected(Expected::Named("identifier"));
+                IResult::Error
+            }
             _ => IResult::Done(rest, id),
         }
     }
@@ -91,11 +139,11 @@ pub mod parsing {
 
         while let Some((i, ch)) = chars.next() {
             if !UnicodeXID::is_xid_continue(ch) {
-                return IResult::Done(input.advance(i), input.until(i).into());
+                return IResult::Done(input.advance(i), located(input, i));
             }
         }
 
-        IResult::Done(input.finish(), input.rest().into())
+        IResult::Done(input.finish(), located(input, input.len()))
     }
 
     #[cfg(feature = "full")]
@@ -107,12 +155,12 @@ pub mod parsing {
                 return if i == 0 {
                     IResult::Error
                 } else {
-                    IResult::Done(input.advance(i), input.until(i).into())
+                    IResult::Done(input.advance(i), located(input, i))
                 };
             }
         }
 
-        IResult::Done(input.finish(), input.rest().into())
+        IResult::Done(input.finish(), located(input, input.len()))
     }
 }
 
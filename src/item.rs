@@ -33,7 +33,7 @@ pub enum ItemKind {
     /// A function declaration (`fn` or `pub fn`).
     ///
     /// E.g. `fn foo(bar: usize) -> usize { .. }`
-    Fn(Box<FnDecl>, Unsafety, Constness, Option<Abi>, Generics, Box<Block>),
+    Fn(Box<FnDecl>, Unsafety, Constness, Asyncness, Option<Abi>, Generics, Box<Block>),
     /// A module declaration (`mod` or `pub mod`).
     ///
     /// E.g. `mod foo;` or `mod foo { .. }`
@@ -79,6 +79,10 @@ pub enum ItemKind {
     ///
     /// E.g. `macro_rules! foo { .. }` or `foo!(..)`
     Mac(Mac),
+    /// A placeholder for source which failed to parse as an item, produced by
+    /// `parse_crate_recovering`. Holds the skipped source, which is printed
+    /// verbatim. The item's span covers the skipped source.
+    Error(String),
 }
 
 impl From<DeriveInput> for Item {
@@ -106,17 +110,16 @@ pub enum ViewPath {
     Simple(Path, Option<Ident>),
 
     /// `foo::bar::*`
+    ///
+    /// The path is empty for a glob without a prefix, like the `*` in
+    /// `foo::{a, *}`.
     Glob(Path),
 
-    /// `foo::bar::{a, b, c}`
-    List(Path, Vec<PathListItem>),
-}
-
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub struct PathListItem {
-    pub name: Ident,
-    /// renamed in list, e.g. `use foo::{bar as baz};`
-    pub rename: Option<Ident>,
+    /// `foo::bar::{a, b as c, self, d::{e, f}, g::*}`
+    ///
+    /// The path is empty for a group without a prefix, like `{a, b}` or
+    /// `::{a, b}`, and the items are relative to the path.
+    List(Path, Vec<ViewPath>),
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -125,6 +128,12 @@ pub enum Constness {
     NotConst,
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Asyncness {
+    Async,
+    NotAsync,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Defaultness {
     Default,
@@ -174,7 +183,7 @@ pub struct TraitItem {
 pub enum TraitItemKind {
     Const(Ty, Option<Expr>),
     Method(MethodSig, Option<Block>),
-    Type(Vec<TyParamBound>, Option<Ty>),
+    Type(Generics, Vec<TyParamBound>, Option<Ty>),
     Macro(Mac),
 }
 
@@ -191,6 +200,8 @@ pub struct ImplItem {
     pub ident: Ident,
     pub vis: Visibility,
     pub defaultness: Defaultness,
+    /// The span of the `default` keyword, if any
+    pub defaultness_span: Option<Span>,
     pub attrs: Vec<Attribute>,
     pub node: ImplItemKind,
     pub span: Span,
@@ -200,7 +211,7 @@ pub struct ImplItem {
 pub enum ImplItemKind {
     Const(Ty, Expr),
     Method(MethodSig, Block),
-    Type(Ty),
+    Type(Generics, Ty),
     Macro(Mac),
 }
 
@@ -210,6 +221,7 @@ pub enum ImplItemKind {
 pub struct MethodSig {
     pub unsafety: Unsafety,
     pub constness: Constness,
+    pub asyncness: Asyncness,
     pub abi: Option<Abi>,
     pub decl: FnDecl,
     pub generics: Generics,
@@ -222,6 +234,8 @@ pub struct MethodSig {
 pub struct FnDecl {
     pub inputs: Vec<FnArg>,
     pub output: FunctionRetTy,
+    /// The span of the `->` before an explicit return type
+    pub rarrow_span: Option<Span>,
     pub variadic: bool,
 }
 
@@ -240,7 +254,7 @@ pub enum FnArg {
 pub mod parsing {
     use super::*;
     use {Block, DelimToken, FunctionRetTy, Generics, Ident, Mac, Path, TokenTree, VariantData,
-         Visibility};
+         VisibilityKind};
     use attr::parsing::{inner_attr, outer_attr};
     use data::parsing::{struct_like_body, visibility};
     use expr::parsing::{expr, pat, within_block};
@@ -295,7 +309,7 @@ pub mod parsing {
         }, punct!(";")) >>
         (Item {
             ident: name.unwrap_or_else(|| Ident::new("")),
-            vis: Visibility::Inherited,
+            vis: VisibilityKind::Inherited.into(),
             attrs: attrs,
             node: ItemKind::Mac(Mac {
                 path: what,
@@ -349,6 +363,8 @@ pub mod parsing {
     named!(view_path -> ViewPath, alt!(
         view_path_glob
         |
+        view_path_glob_root
+        |
         view_path_list
         |
         view_path_list_root
@@ -356,6 +372,8 @@ pub mod parsing {
         view_path_simple // must be last
     ));
 
+    // Only the outermost path may start with `::`
+    named!(view_path_nested -> ViewPath, preceded!(not!(punct!("::")), view_path));
 
     named!(view_path_simple -> ViewPath, do_parse!(
         path: path >>
@@ -370,37 +388,40 @@ pub mod parsing {
         (ViewPath::Glob(path))
     ));
 
+    named!(view_path_glob_root -> ViewPath, do_parse!(
+        path: view_path_root >>
+        punct!("*") >>
+        (ViewPath::Glob(path))
+    ));
+
     named!(view_path_list -> ViewPath, do_parse!(
         path: path >>
         punct!("::") >>
-        punct!("{") >>
-        items: terminated_list!(punct!(","), path_list_item) >>
-        punct!("}") >>
+        items: view_path_group >>
         (ViewPath::List(path, items))
     ));
 
     named!(view_path_list_root -> ViewPath, do_parse!(
-        global: option!(punct!("::")) >>
-        punct!("{") >>
-        items: terminated_list!(punct!(","), path_list_item) >>
-        punct!("}") >>
-        (ViewPath::List(Path {
+        path: view_path_root >>
+        items: view_path_group >>
+        (ViewPath::List(path, items))
+    ));
+
+    // The empty path, with an optional leading `::`, of a group or glob
+    // without a prefix
+    named!(view_path_root -> Path, map!(
+        option!(spanned!(punct!("::"))),
+        |global: Option<Spanned<&str>>| Path {
             global: global.is_some(),
             segments: Vec::new(),
-        }, items))
+            span: global.map_or(EMPTY_SPAN, |global| global.span),
+        }
     ));
 
-    named!(path_list_item -> PathListItem, do_parse!(
-        name: alt!(
-            ident
-            |
-            map!(keyword!("self"), Into::into)
-        ) >>
-        rename: option!(preceded!(keyword!("as"), ident)) >>
-        (PathListItem {
-            name: name,
-            rename: rename,
-        })
+    named!(view_path_group -> Vec<ViewPath>, delimited!(
+        punct!("{"),
+        terminated_list!(punct!(","), view_path_nested),
+        punct!("}")
     ));
 
     named!(item_static -> Item, do_parse!(
@@ -446,6 +467,7 @@ pub mod parsing {
         outer_attrs: many0!(outer_attr) >>
         vis: visibility >>
         constness: constness >>
+        asyncness: asyncness >>
         unsafety: unsafety >>
         abi: option!(abi) >>
         keyword!("fn") >>
@@ -454,35 +476,40 @@ pub mod parsing {
         punct!("(") >>
         inputs: terminated_list!(punct!(","), fn_arg) >>
         punct!(")") >>
-        ret: option!(preceded!(punct!("->"), ty)) >>
+        ret: option!(tuple!(spanned!(punct!("->")), ty)) >>
         where_clause: where_clause >>
-        punct!("{") >>
-        inner_attrs: many0!(inner_attr) >>
-        stmts: within_block >>
-        punct!("}") >>
+        body: spanned!(delimited!(
+            punct!("{"),
+            tuple!(many0!(inner_attr), within_block),
+            punct!("}")
+        )) >>
         (Item {
             ident: name,
             vis: vis,
             attrs: {
                 let mut attrs = outer_attrs;
-                attrs.extend(inner_attrs);
+                attrs.extend(body.node.0);
                 attrs
             },
             node: ItemKind::Fn(
                 Box::new(FnDecl {
                     inputs: inputs,
-                    output: ret.map(FunctionRetTy::Ty).unwrap_or(FunctionRetTy::Default),
+                    rarrow_span: ret.as_ref().map(|ret| ret.0.span),
+                    output: ret.map(|ret| FunctionRetTy::Ty(ret.1))
+                        .unwrap_or(FunctionRetTy::Default),
                     variadic: false,
                 }),
                 unsafety,
                 constness,
+                asyncness,
                 abi,
                 Generics {
                     where_clause: where_clause,
                     .. generics
                 },
                 Box::new(Block {
-                    stmts: stmts,
+                    stmts: body.node.1,
+                    span: body.span,
                 }),
             ),
             span: DUMMY_SPAN,
@@ -563,7 +590,7 @@ pub mod parsing {
         punct!("}") >>
         (Item {
             ident: "".into(),
-            vis: Visibility::Inherited,
+            vis: VisibilityKind::Inherited.into(),
             attrs: attrs,
             node: ItemKind::ForeignMod(ForeignMod {
                 abi: abi,
@@ -590,7 +617,7 @@ pub mod parsing {
         trailing_comma: option!(punct!(",")) >>
         variadic: option!(cond_reduce!(trailing_comma.is_some(), punct!("..."))) >>
         punct!(")") >>
-        ret: option!(preceded!(punct!("->"), ty)) >>
+        ret: option!(tuple!(spanned!(punct!("->")), ty)) >>
         where_clause: where_clause >>
         punct!(";") >>
         (ForeignItem {
@@ -599,7 +626,9 @@ pub mod parsing {
             node: ForeignItemKind::Fn(
                 Box::new(FnDecl {
                     inputs: inputs,
-                    output: ret.map(FunctionRetTy::Ty).unwrap_or(FunctionRetTy::Default),
+                    rarrow_span: ret.as_ref().map(|ret| ret.0.span),
+                    output: ret.map(|ret| FunctionRetTy::Ty(ret.1))
+                        .unwrap_or(FunctionRetTy::Default),
                     variadic: variadic.is_some(),
                 }),
                 Generics {
@@ -739,7 +768,7 @@ pub mod parsing {
         punct!("}") >>
         (Item {
             ident: "".into(),
-            vis: Visibility::Inherited,
+            vis: VisibilityKind::Inherited.into(),
             attrs: attrs,
             node: ItemKind::DefaultImpl(unsafety, path),
             span: DUMMY_SPAN,
@@ -775,6 +804,7 @@ pub mod parsing {
     named!(trait_item_method -> TraitItem, do_parse!(
         outer_attrs: many0!(outer_attr) >>
         constness: constness >>
+        asyncness: asyncness >>
         unsafety: unsafety >>
         abi: option!(abi) >>
         keyword!("fn") >>
@@ -783,17 +813,22 @@ pub mod parsing {
         punct!("(") >>
         inputs: terminated_list!(punct!(","), fn_arg) >>
         punct!(")") >>
-        ret: option!(preceded!(punct!("->"), ty)) >>
+        ret: option!(tuple!(spanned!(punct!("->")), ty)) >>
         where_clause: where_clause >>
-        body: option!(delimited!(
+        body: option!(spanned!(delimited!(
             punct!("{"),
             tuple!(many0!(inner_attr), within_block),
             punct!("}")
-        )) >>
+        ))) >>
         cond!(body.is_none(), punct!(";")) >>
         ({
-            let (inner_attrs, stmts) = match body {
-                Some((inner_attrs, stmts)) => (inner_attrs, Some(stmts)),
+            let (inner_attrs, block) = match body {
+                Some(Spanned { node: (inner_attrs, stmts), span }) => {
+                    (inner_attrs, Some(Block {
+                        stmts: stmts,
+                        span: span,
+                    }))
+                }
                 None => (Vec::new(), None),
             };
             TraitItem {
@@ -807,10 +842,13 @@ pub mod parsing {
                     MethodSig {
                         unsafety: unsafety,
                         constness: constness,
+                        asyncness: asyncness,
                         abi: abi,
                         decl: FnDecl {
                             inputs: inputs,
-                            output: ret.map(FunctionRetTy::Ty).unwrap_or(FunctionRetTy::Default),
+                            rarrow_span: ret.as_ref().map(|ret| ret.0.span),
+                            output: ret.map(|ret| FunctionRetTy::Ty(ret.1))
+                                .unwrap_or(FunctionRetTy::Default),
                             variadic: false,
                         },
                         generics: Generics {
@@ -818,7 +856,7 @@ pub mod parsing {
                             .. generics
                         },
                     },
-                    stmts.map(|stmts| Block { stmts: stmts }),
+                    block,
                 ),
                 span: DUMMY_SPAN,
             }
@@ -829,16 +867,26 @@ pub mod parsing {
         attrs: many0!(outer_attr) >>
         keyword!("type") >>
         id: ident >>
+        generics: generics >>
         bounds: opt_vec!(preceded!(
             punct!(":"),
             separated_nonempty_list!(punct!("+"), ty_param_bound)
         )) >>
+        before_default: where_clause >>
         default: option!(preceded!(punct!("="), ty)) >>
+        after_default: cond!(before_default.where_span.is_none(), where_clause) >>
         punct!(";") >>
         (TraitItem {
             ident: id,
             attrs: attrs,
-            node: TraitItemKind::Type(bounds, default),
+            node: TraitItemKind::Type(
+                Generics {
+                    where_clause: after_default.unwrap_or(before_default),
+                    ..generics
+                },
+                bounds,
+                default,
+            ),
             span: DUMMY_SPAN,
         })
     ));
@@ -885,7 +933,7 @@ pub mod parsing {
         punct!("}") >>
         (Item {
             ident: "".into(),
-            vis: Visibility::Inherited,
+            vis: VisibilityKind::Inherited.into(),
             attrs: attrs,
             node: ItemKind::Impl(
                 unsafety,
@@ -926,7 +974,8 @@ pub mod parsing {
         (ImplItem {
             ident: id,
             vis: vis,
-            defaultness: defaultness,
+            defaultness: defaultness.0,
+            defaultness_span: defaultness.1,
             attrs: attrs,
             node: ImplItemKind::Const(ty, value),
             span: DUMMY_SPAN,
@@ -938,6 +987,7 @@ pub mod parsing {
         vis: visibility >>
         defaultness: defaultness >>
         constness: constness >>
+        asyncness: asyncness >>
         unsafety: unsafety >>
         abi: option!(abi) >>
         keyword!("fn") >>
@@ -946,29 +996,34 @@ pub mod parsing {
         punct!("(") >>
         inputs: terminated_list!(punct!(","), fn_arg) >>
         punct!(")") >>
-        ret: option!(preceded!(punct!("->"), ty)) >>
+        ret: option!(tuple!(spanned!(punct!("->")), ty)) >>
         where_clause: where_clause >>
-        punct!("{") >>
-        inner_attrs: many0!(inner_attr) >>
-        stmts: within_block >>
-        punct!("}") >>
+        body: spanned!(delimited!(
+            punct!("{"),
+            tuple!(many0!(inner_attr), within_block),
+            punct!("}")
+        )) >>
         (ImplItem {
             ident: name,
             vis: vis,
-            defaultness: defaultness,
+            defaultness: defaultness.0,
+            defaultness_span: defaultness.1,
             attrs: {
                 let mut attrs = outer_attrs;
-                attrs.extend(inner_attrs);
+                attrs.extend(body.node.0);
                 attrs
             },
             node: ImplItemKind::Method(
                 MethodSig {
                     unsafety: unsafety,
                     constness: constness,
+                    asyncness: asyncness,
                     abi: abi,
                     decl: FnDecl {
                         inputs: inputs,
-                        output: ret.map(FunctionRetTy::Ty).unwrap_or(FunctionRetTy::Default),
+                        rarrow_span: ret.as_ref().map(|ret| ret.0.span),
+                        output: ret.map(|ret| FunctionRetTy::Ty(ret.1))
+                            .unwrap_or(FunctionRetTy::Default),
                         variadic: false,
                     },
                     generics: Generics {
@@ -977,7 +1032,8 @@ pub mod parsing {
                     },
                 },
                 Block {
-                    stmts: stmts,
+                    stmts: body.node.1,
+                    span: body.span,
                 },
             ),
             span: DUMMY_SPAN,
@@ -990,15 +1046,25 @@ pub mod parsing {
         defaultness: defaultness >>
         keyword!("type") >>
         id: ident >>
+        generics: generics >>
+        before_ty: where_clause >>
         punct!("=") >>
         ty: ty >>
+        after_ty: cond!(before_ty.where_span.is_none(), where_clause) >>
         punct!(";") >>
         (ImplItem {
             ident: id,
             vis: vis,
-            defaultness: defaultness,
+            defaultness: defaultness.0,
+            defaultness_span: defaultness.1,
             attrs: attrs,
-            node: ImplItemKind::Type(ty),
+            node: ImplItemKind::Type(
+                Generics {
+                    where_clause: after_ty.unwrap_or(before_ty),
+                    ..generics
+                },
+                ty,
+            ),
             span: DUMMY_SPAN,
         })
     ));
@@ -1014,8 +1080,9 @@ pub mod parsing {
         }, punct!(";")) >>
         (ImplItem {
             ident: Ident::new(""),
-            vis: Visibility::Inherited,
+            vis: VisibilityKind::Inherited.into(),
             defaultness: Defaultness::Final,
+            defaultness_span: None,
             attrs: attrs,
             node: ImplItemKind::Macro(Mac {
                 path: what,
@@ -1037,10 +1104,18 @@ pub mod parsing {
         epsilon!() => { |_| Constness::NotConst }
     ));
 
-    named!(defaultness -> Defaultness, alt!(
-        keyword!("default") => { |_| Defaultness::Default }
+    named!(pub asyncness -> Asyncness, alt!(
+        keyword!("async") => { |_| Asyncness::Async }
         |
-        epsilon!() => { |_| Defaultness::Final }
+        epsilon!() => { |_| Asyncness::NotAsync }
+    ));
+
+    named!(defaultness -> (Defaultness, Option<Span>), alt!(
+        spanned!(keyword!("default")) => {
+            |default: Spanned<&str>| (Defaultness::Default, Some(default.span))
+        }
+        |
+        epsilon!() => { |_| (Defaultness::Final, None) }
     ));
 }
 
@@ -1094,9 +1169,16 @@ mod printing {
                     expr.to_tokens(tokens);
                     tokens.append(";");
                 }
-                ItemKind::Fn(ref decl, unsafety, constness, ref abi, ref generics, ref block) => {
+                ItemKind::Fn(ref decl,
+                             unsafety,
+                             constness,
+                             asyncness,
+                             ref abi,
+                             ref generics,
+                             ref block) => {
                     self.vis.to_tokens(tokens);
                     constness.to_tokens(tokens);
+                    asyncness.to_tokens(tokens);
                     unsafety.to_tokens(tokens);
                     abi.to_tokens(tokens);
                     tokens.append("fn");
@@ -1242,6 +1324,7 @@ mod printing {
                         _ => tokens.append(";"),
                     }
                 }
+                ItemKind::Error(ref source) => tokens.append(source),
             }
         }
     }
@@ -1258,7 +1341,9 @@ mod printing {
                 }
                 ViewPath::Glob(ref path) => {
                     path.to_tokens(tokens);
-                    tokens.append("::");
+                    if path.global || !path.segments.is_empty() {
+                        tokens.append("::");
+                    }
                     tokens.append("*");
                 }
                 ViewPath::List(ref path, ref items) => {
@@ -1274,16 +1359,6 @@ mod printing {
         }
     }
 
-    impl ToTokens for PathListItem {
-        fn to_tokens(&self, tokens: &mut Tokens) {
-            self.name.to_tokens(tokens);
-            if let Some(ref rename) = self.rename {
-                tokens.append("as");
-                rename.to_tokens(tokens);
-            }
-        }
-    }
-
     impl ToTokens for TraitItem {
         fn to_tokens(&self, tokens: &mut Tokens) {
             tokens.append_all(self.attrs.outer());
@@ -1301,6 +1376,7 @@ mod printing {
                 }
                 TraitItemKind::Method(ref sig, ref block) => {
                     sig.constness.to_tokens(tokens);
+                    sig.asyncness.to_tokens(tokens);
                     sig.unsafety.to_tokens(tokens);
                     sig.abi.to_tokens(tokens);
                     tokens.append("fn");
@@ -1324,9 +1400,10 @@ mod printing {
                         None => tokens.append(";"),
                     }
                 }
-                TraitItemKind::Type(ref bound, ref default) => {
+                TraitItemKind::Type(ref generics, ref bound, ref default) => {
                     tokens.append("type");
                     self.ident.to_tokens(tokens);
+                    generics.to_tokens(tokens);
                     if !bound.is_empty() {
                         tokens.append(":");
                         tokens.append_separated(bound, "+");
@@ -1335,6 +1412,7 @@ mod printing {
                         tokens.append("=");
                         default.to_tokens(tokens);
                     }
+                    generics.where_clause.to_tokens(tokens);
                     tokens.append(";");
                 }
                 TraitItemKind::Macro(ref mac) => {
@@ -1369,6 +1447,7 @@ mod printing {
                     self.vis.to_tokens(tokens);
                     self.defaultness.to_tokens(tokens);
                     sig.constness.to_tokens(tokens);
+                    sig.asyncness.to_tokens(tokens);
                     sig.unsafety.to_tokens(tokens);
                     sig.abi.to_tokens(tokens);
                     tokens.append("fn");
@@ -1387,13 +1466,15 @@ mod printing {
                     tokens.append_all(&block.stmts);
                     tokens.append("}");
                 }
-                ImplItemKind::Type(ref ty) => {
+                ImplItemKind::Type(ref generics, ref ty) => {
                     self.vis.to_tokens(tokens);
                     self.defaultness.to_tokens(tokens);
                     tokens.append("type");
                     self.ident.to_tokens(tokens);
+                    generics.to_tokens(tokens);
                     tokens.append("=");
                     ty.to_tokens(tokens);
+                    generics.where_clause.to_tokens(tokens);
                     tokens.append(";");
                 }
                 ImplItemKind::Macro(ref mac) => {
@@ -1483,6 +1564,17 @@ mod printing {
         }
     }
 
+    impl ToTokens for Asyncness {
+        fn to_tokens(&self, tokens: &mut Tokens) {
+            match *self {
+                Asyncness::Async => tokens.append("async"),
+                Asyncness::NotAsync => {
+                    // nothing
+                }
+            }
+        }
+    }
+
     impl ToTokens for Defaultness {
         fn to_tokens(&self, tokens: &mut Tokens) {
             match *self {
@@ -11,19 +11,133 @@ pub struct Crate {
 pub mod parsing {
     use super::*;
     use attr::parsing::inner_attr;
-    use item::parsing::items;
+    use error;
+    use item::parsing::{item, items};
+    use mac::parsing::token_tree;
+    use synom::{FurthestFailure, IResult, ParseState};
+    use synom::space::skip_whitespace;
 
     named!(pub krate -> Crate, do_parse!(
+        header: krate_header >>
+        items: items >>
+        (Crate {
+            shebang: header.0,
+            attrs: header.1,
+            items: items,
+        })
+    ));
+
+    named!(krate_header -> (Option<String>, Vec<Attribute>), do_parse!(
         option!(byte_order_mark) >>
         shebang: option!(shebang) >>
         attrs: many0!(inner_attr) >>
-        items: items >>
-        (Crate {
+        (shebang, attrs)
+    ));
+
+    pub fn krate_recovering(input: &str) -> (Crate, Vec<ParseError>) {
+        let (mut rest, (shebang, attrs)) = match krate_header(ParseState::new(input)) {
+            IResult::Done(rest, header) => (rest, header),
+            IResult::Error => unreachable!(),
+        };
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+        loop {
+            rest = skip_whitespace(rest);
+            if rest.is_empty() {
+                break;
+            }
+            let failure = FurthestFailure::new();
+            let start = ParseState::tracking(input, &failure).advance(rest.idx());
+            match item(start) {
+                IResult::Done(after, item) => {
+                    items.push(item);
+                    rest = rest.advance(after.idx() - rest.idx());
+                }
+                IResult::Error => {
+                    errors.push(error::from_failure("item", input, start, None, &failure));
+                    let furthest = failure.position().unwrap_or(rest.idx());
+                    let end = skip_item(rest, furthest);
+                    items.push(Item {
+                        ident: Ident::new(""),
+                        vis: VisibilityKind::Inherited.into(),
+                        attrs: Vec::new(),
+                        node: ItemKind::Error(input[rest.idx()..end.idx()].to_owned()),
+                        span: Span {
+                            lo: rest.idx(),
+                            hi: end.idx(),
+                        },
+                    });
+                    rest = end;
+                }
+            }
+        }
+        let krate = Crate {
             shebang: shebang,
             attrs: attrs,
             items: items,
-        })
-    ));
+        };
+        (krate, errors)
+    }
+
+    /// Skips over the token trees of an item which failed to parse. Skipping
+    /// stops after the first `;` or braced group which ends past `furthest`,
+    /// the position at which parsing failed, or before the next token past
+    /// `furthest` which can only start a new item.
+    fn skip_item(mut input: ParseState, furthest: usize) -> ParseState {
+        let mut first = true;
+        loop {
+            let next = skip_whitespace(input);
+            if next.is_empty() {
+                return input;
+            }
+            if !first && next.idx() >= furthest {
+                if let IResult::Done(_, _) = item_start(next) {
+                    return input;
+                }
+            }
+            first = false;
+            input = match token_tree(next) {
+                IResult::Done(rest, TokenTree::Token(Token::Semi, _)) |
+                IResult::Done(rest, TokenTree::Delimited(Delimited { delim: DelimToken::Brace, .. }, _))
+                    if rest.idx() > furthest => return rest,
+                IResult::Done(rest, _) => rest,
+                // Not a token, or an unbalanced delimiter
+                IResult::Error => next.advance(next.chars().next().unwrap().len_utf8()),
+            };
+        }
+    }
+
+    named!(item_start -> &str, peek!(alt!(
+        punct!("#")
+        |
+        keyword!("pub")
+        |
+        keyword!("extern")
+        |
+        keyword!("use")
+        |
+        keyword!("static")
+        |
+        keyword!("const")
+        |
+        keyword!("async")
+        |
+        keyword!("unsafe")
+        |
+        keyword!("fn")
+        |
+        keyword!("mod")
+        |
+        keyword!("type")
+        |
+        keyword!("struct")
+        |
+        keyword!("enum")
+        |
+        keyword!("trait")
+        |
+        keyword!("impl")
+    )));
 
     named!(byte_order_mark -> &str, tag!("\u{feff}"));
 
@@ -19,13 +19,24 @@ mod span;
 pub use span::{Span, Spanned, EMPTY_SPAN, DUMMY_SPAN};
 
 mod attr;
-pub use attr::{Attribute, AttrStyle, MetaItem, NestedMetaItem};
+pub use attr::{Attribute, AttrStyle, MetaItem, MetaItemKind, NestedMetaItem,
+               NestedMetaItemKind};
 
 mod constant;
 pub use constant::ConstExpr;
 
 mod data;
-pub use data::{Field, Variant, VariantData, Visibility};
+pub use data::{Field, Variant, VariantData, Visibility, VisibilityKind};
+
+mod diagnostic;
+pub use diagnostic::{Diagnostic, Errors, Severity};
+
+#[cfg(feature = "parsing")]
+mod error;
+#[cfg(feature = "parsing")]
+pub use error::ParseError;
+#[cfg(feature = "parsing")]
+pub use synom::Expected;
 
 #[cfg(feature = "parsing")]
 mod escape;
@@ -34,23 +45,26 @@ mod escape;
 mod expr;
 #[cfg(feature = "full")]
 pub use expr::{Arm, BindingMode, Block, CaptureBy, Expr, ExprKind, FieldPat, FieldValue,
-               Local, MacStmtStyle, Pat, RangeLimits, Stmt};
+               Local, MacStmtStyle, Pat, PatKind, RangeLimits, Stmt, StmtKind};
 
 mod generics;
-pub use generics::{Generics, Lifetime, LifetimeDef, TraitBoundModifier, TyParam, TyParamBound,
-                   WhereBoundPredicate, WhereClause, WhereEqPredicate, WherePredicate,
-                   WhereRegionPredicate};
+pub use generics::{ConstParam, GenericParam, Generics, Lifetime, LifetimeDef, TraitBoundModifier,
+                   TyParam, TyParamBound, WhereBoundPredicate, WhereClause, WhereEqPredicate,
+                   WherePredicate, WhereRegionPredicate};
 #[cfg(feature = "printing")]
 pub use generics::{ImplGenerics, Turbofish, TyGenerics};
 
 mod ident;
 pub use ident::Ident;
 
+mod has_span;
+pub use has_span::HasSpan;
+
 #[cfg(feature = "full")]
 mod item;
 #[cfg(feature = "full")]
-pub use item::{Constness, Defaultness, FnArg, FnDecl, ForeignItemKind, ForeignItem, ForeignMod,
-               ImplItem, ImplItemKind, ImplPolarity, Item, ItemKind, MethodSig, PathListItem,
+pub use item::{Asyncness, Constness, Defaultness, FnArg, FnDecl, ForeignItemKind, ForeignItem,
+               ForeignMod, ImplItem, ImplItemKind, ImplPolarity, Item, ItemKind, MethodSig,
                TraitItem, TraitItemKind, ViewPath};
 
 #[cfg(feature = "full")]
@@ -66,6 +80,11 @@ pub use lit::{ByteStrLit, FloatLit, IntLit, StrLit};
 mod mac;
 pub use mac::{BinOpToken, DelimToken, Delimited, Mac, Token, TokenTree};
 
+#[cfg(all(feature = "full", feature = "visit"))]
+mod node;
+#[cfg(all(feature = "full", feature = "visit"))]
+pub use node::Node;
+
 mod derive;
 pub use derive::{Body, DeriveInput};
 // Deprecated. Use `DeriveInput` instead.
@@ -75,10 +94,23 @@ pub type MacroInput = DeriveInput;
 mod op;
 pub use op::{BinOp, UnOp};
 
+#[cfg(feature = "parsing")]
+mod rewrite;
+#[cfg(feature = "parsing")]
+pub use rewrite::{Rewriter, TextEdit};
+
+mod source_map;
+pub use source_map::{FileId, Loc, SourceFile, SourceMap, SpanLoc};
+
+#[cfg(feature = "parsing")]
+mod trivia;
+#[cfg(feature = "parsing")]
+pub use trivia::Trivia;
+
 mod ty;
-pub use ty::{Abi, AngleBracketedParameterData, BareFnArg, BareFnTy, FunctionRetTy, MutTy,
-             Mutability, ParenthesizedParameterData, Path, PathParameters, PathSegment,
-             PolyTraitRef, QSelf, Ty, TypeBinding, Unsafety};
+pub use ty::{Abi, AngleBracketedParameterData, BareFnArg, BareFnTy, FunctionRetTy, GenericArg,
+             MutTy, Mutability, ParenthesizedParameterData, Path, PathParameters, PathSegment,
+             PolyTraitRef, QSelf, TraitObjectSyntax, Ty, TyKind, TypeBinding, Unsafety};
 
 #[cfg(feature = "visit")]
 pub mod visit;
@@ -92,83 +124,101 @@ pub use parsing::*;
 #[cfg(feature = "parsing")]
 mod parsing {
     use super::*;
-    use {derive, generics, ident, mac, ty};
-    use synom::{space, IResult, ParseState};
+    use {derive, error, generics, ident, mac, ty};
+    use synom::{FurthestFailure, IResult, ParseState};
+    use synom::space::skip_whitespace;
 
     #[cfg(feature = "full")]
     use {expr, item, krate};
 
-    pub fn parse_derive_input(input: &str) -> Result<DeriveInput, String> {
+    pub fn parse_derive_input(input: &str) -> Result<DeriveInput, ParseError> {
         unwrap("derive input", derive::parsing::derive_input, input)
     }
 
     #[cfg(feature = "full")]
-    pub fn parse_crate(input: &str) -> Result<Crate, String> {
+    pub fn parse_crate(input: &str) -> Result<Crate, ParseError> {
         unwrap("crate", krate::parsing::krate, input)
     }
 
+    /// Parses a crate, recovering from items which fail to parse.
+    ///
+    /// Each item which fails to parse is skipped up to the next item boundary
+    /// and replaced by an `ItemKind::Error` placeholder holding the skipped
+    /// source, so the crate still prints back all of its input. The crate is
+    /// returned together with an error for each skipped item.
+    #[cfg(feature = "full")]
+    pub fn parse_crate_recovering(input: &str) -> (Crate, Vec<ParseError>) {
+        krate::parsing::krate_recovering(input)
+    }
+
+    /// Parses a crate together with the whitespace and comments between its
+    /// tokens, which `Trivia::print` uses to print the crate losslessly.
+    #[cfg(feature = "full")]
+    pub fn parse_crate_lossless(input: &str) -> Result<(Crate, Trivia), ParseError> {
+        let krate = try!(parse_crate(input));
+        Ok((krate, try!(Trivia::new(input))))
+    }
+
     #[cfg(feature = "full")]
-    pub fn parse_item(input: &str) -> Result<Item, String> {
+    pub fn parse_item(input: &str) -> Result<Item, ParseError> {
         unwrap("item", item::parsing::item, input)
     }
 
     #[cfg(feature = "full")]
-    pub fn parse_items(input: &str) -> Result<Vec<Item>, String> {
+    pub fn parse_items(input: &str) -> Result<Vec<Item>, ParseError> {
         unwrap("items", item::parsing::items, input)
     }
 
     #[cfg(feature = "full")]
-    pub fn parse_expr(input: &str) -> Result<Expr, String> {
+    pub fn parse_expr(input: &str) -> Result<Expr, ParseError> {
         unwrap("expression", expr::parsing::expr, input)
     }
 
-    pub fn parse_type(input: &str) -> Result<Ty, String> {
+    pub fn parse_type(input: &str) -> Result<Ty, ParseError> {
         unwrap("type", ty::parsing::ty, input)
     }
 
-    pub fn parse_path(input: &str) -> Result<Path, String> {
+    pub fn parse_path(input: &str) -> Result<Path, ParseError> {
         unwrap("path", ty::parsing::path, input)
     }
 
-    pub fn parse_where_clause(input: &str) -> Result<WhereClause, String> {
+    pub fn parse_where_clause(input: &str) -> Result<WhereClause, ParseError> {
         unwrap("where clause", generics::parsing::where_clause, input)
     }
 
-    pub fn parse_token_trees(input: &str) -> Result<Vec<TokenTree>, String> {
+    pub fn parse_token_trees(input: &str) -> Result<Vec<TokenTree>, ParseError> {
         unwrap("token trees", mac::parsing::token_trees, input)
     }
 
-    pub fn parse_ident(input: &str) -> Result<Ident, String> {
+    pub fn parse_ident(input: &str) -> Result<Ident, ParseError> {
         unwrap("identifier", ident::parsing::ident, input)
     }
 
-    pub fn parse_ty_param_bound(input: &str) -> Result<TyParamBound, String> {
+    pub fn parse_ty_param_bound(input: &str) -> Result<TyParamBound, ParseError> {
         unwrap("type parameter bound", generics::parsing::ty_param_bound, input)
     }
 
     // Deprecated. Use `parse_derive_input` instead.
     #[doc(hidden)]
-    pub fn parse_macro_input(input: &str) -> Result<MacroInput, String> {
+    pub fn parse_macro_input(input: &str) -> Result<MacroInput, ParseError> {
         parse_derive_input(input)
     }
 
     fn unwrap<T>(name: &'static str,
                  f: fn(ParseState) -> IResult<ParseState, T>,
                  input: &str)
-                 -> Result<T, String> {
-        match f(ParseState::new(input)) {
-            IResult::Done(mut rest, t) => {
-                rest = space::skip_whitespace(rest);
-                if rest.is_empty() {
+                 -> Result<T, ParseError> {
+        let failure = FurthestFailure::new();
+        let state = ParseState::tracking(input, &failure);
+        match f(state) {
+            IResult::Done(rest, t) => {
+                if skip_whitespace(rest).is_empty() {
                     Ok(t)
-                } else if rest.len() == input.len() {
-                    // parsed nothing
-                    Err(format!("failed to parse {}: {:?}", name, rest))
                 } else {
-                    Err(format!("unparsed tokens after {}: {:?}", name, rest))
+                    Err(error::from_failure(name, input, state, Some(rest), &failure))
                 }
             }
-            IResult::Error => Err(format!("failed to parse {}: {:?}", name, input)),
+            IResult::Error => Err(error::from_failure(name, input, state, None, &failure)),
         }
     }
 }
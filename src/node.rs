@@ -0,0 +1,215 @@
+//! Mapping source offsets back to the syntax tree.
+
+use super::*;
+use visit::{self, Visit};
+
+/// A reference to a node of the syntax tree.
+#[derive(Debug, Clone, Copy)]
+pub enum Node<'a> {
+    Item(&'a Item),
+    ForeignItem(&'a ForeignItem),
+    TraitItem(&'a TraitItem),
+    ImplItem(&'a ImplItem),
+    Variant(&'a Variant),
+    Field(&'a Field),
+    Generics(&'a Generics),
+    FnDecl(&'a FnDecl),
+    Block(&'a Block),
+    Stmt(&'a Stmt),
+    Local(&'a Local),
+    Expr(&'a Expr),
+    Pat(&'a Pat),
+    Ty(&'a Ty),
+    Path(&'a Path),
+    PathSegment(&'a PathSegment),
+    Mac(&'a Mac),
+    Attribute(&'a Attribute),
+    Lifetime(&'a Lifetime),
+    Lit(&'a Lit),
+    Ident(&'a Ident),
+}
+
+impl<'a> HasSpan for Node<'a> {
+    fn span(&self) -> Option<Span> {
+        match *self {
+            Node::Item(node) => node.span(),
+            Node::ForeignItem(node) => node.span(),
+            Node::TraitItem(node) => node.span(),
+            Node::ImplItem(node) => node.span(),
+            Node::Variant(node) => node.span(),
+            Node::Field(node) => node.span(),
+            Node::Generics(node) => node.span(),
+            Node::FnDecl(node) => node.span(),
+            Node::Block(node) => node.span(),
+            Node::Stmt(node) => node.span(),
+            Node::Local(node) => node.span(),
+            Node::Expr(node) => node.span(),
+            Node::Pat(node) => node.span(),
+            Node::Ty(node) => node.span(),
+            Node::Path(node) => node.span(),
+            Node::PathSegment(node) => node.span(),
+            Node::Mac(node) => node.span(),
+            Node::Attribute(node) => node.span(),
+            Node::Lifetime(node) => node.span(),
+            Node::Lit(node) => node.span(),
+            Node::Ident(node) => node.span(),
+        }
+    }
+}
+
+impl Crate {
+    /// The chain of nodes whose spans contain the byte `offset`, from the
+    /// outermost item to the innermost node.
+    ///
+    /// A span contains the offsets from its `lo` up to but not including its
+    /// `hi`.
+    pub fn nodes_at<'a>(&'a self, offset: usize) -> Vec<Node<'a>> {
+        let mut finder = NodesAt::new(offset);
+        finder.visit_crate(self);
+        finder.nodes
+    }
+
+    /// The innermost node whose span contains the byte `offset`.
+    pub fn node_at<'a>(&'a self, offset: usize) -> Option<Node<'a>> {
+        self.nodes_at(offset).pop()
+    }
+}
+
+impl Item {
+    /// The chain of nodes whose spans contain the byte `offset`, starting
+    /// with this item if it contains the offset.
+    pub fn nodes_at<'a>(&'a self, offset: usize) -> Vec<Node<'a>> {
+        let mut finder = NodesAt::new(offset);
+        finder.visit_item(self);
+        finder.nodes
+    }
+
+    /// The innermost node whose span contains the byte `offset`.
+    pub fn node_at<'a>(&'a self, offset: usize) -> Option<Node<'a>> {
+        self.nodes_at(offset).pop()
+    }
+}
+
+impl Expr {
+    /// The chain of nodes whose spans contain the byte `offset`, starting
+    /// with this expression if it contains the offset.
+    pub fn nodes_at<'a>(&'a self, offset: usize) -> Vec<Node<'a>> {
+        let mut finder = NodesAt::new(offset);
+        finder.visit_expr(self);
+        finder.nodes
+    }
+
+    /// The innermost node whose span contains the byte `offset`.
+    pub fn node_at<'a>(&'a self, offset: usize) -> Option<Node<'a>> {
+        self.nodes_at(offset).pop()
+    }
+}
+
+/// Collects the nodes containing an offset in the order they are visited,
+/// which puts every node before its children.
+struct NodesAt<'a> {
+    offset: usize,
+    nodes: Vec<Node<'a>>,
+}
+
+impl<'a> NodesAt<'a> {
+    fn new(offset: usize) -> Self {
+        NodesAt {
+            offset: offset,
+            nodes: Vec::new(),
+        }
+    }
+
+    fn enter(&mut self, node: Node<'a>) {
+        if let Some(span) = node.span() {
+            if span.lo <= self.offset && self.offset < span.hi {
+                self.nodes.push(node);
+            }
+        }
+    }
+}
+
+// Children are walked even when their parent doesn't contain the offset, as
+// some nodes, e.g. a `Generics` and its `where` clause, don't nest.
+impl<'a> Visit<'a> for NodesAt<'a> {
+    fn visit_ident(&mut self, ident: &'a Ident) {
+        self.enter(Node::Ident(ident));
+    }
+    fn visit_ty(&mut self, ty: &'a Ty) {
+        self.enter(Node::Ty(ty));
+        visit::walk_ty(self, ty);
+    }
+    fn visit_generics(&mut self, generics: &'a Generics) {
+        self.enter(Node::Generics(generics));
+        visit::walk_generics(self, generics);
+    }
+    fn visit_field(&mut self, field: &'a Field) {
+        self.enter(Node::Field(field));
+        visit::walk_field(self, field);
+    }
+    fn visit_variant(&mut self, variant: &'a Variant, generics: &'a Generics) {
+        self.enter(Node::Variant(variant));
+        visit::walk_variant(self, variant, generics);
+    }
+    fn visit_lifetime(&mut self, lifetime: &'a Lifetime) {
+        self.enter(Node::Lifetime(lifetime));
+    }
+    fn visit_path(&mut self, path: &'a Path) {
+        self.enter(Node::Path(path));
+        visit::walk_path(self, path);
+    }
+    fn visit_path_segment(&mut self, segment: &'a PathSegment) {
+        self.enter(Node::PathSegment(segment));
+        visit::walk_path_segment(self, segment);
+    }
+    fn visit_attribute(&mut self, attr: &'a Attribute) {
+        self.enter(Node::Attribute(attr));
+    }
+    fn visit_lit(&mut self, lit: &'a Lit) {
+        self.enter(Node::Lit(lit));
+    }
+    fn visit_mac(&mut self, mac: &'a Mac) {
+        self.enter(Node::Mac(mac));
+        visit::walk_mac(self, mac);
+    }
+    fn visit_item(&mut self, item: &'a Item) {
+        self.enter(Node::Item(item));
+        visit::walk_item(self, item);
+    }
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        self.enter(Node::Expr(expr));
+        visit::walk_expr(self, expr);
+    }
+    fn visit_foreign_item(&mut self, foreign_item: &'a ForeignItem) {
+        self.enter(Node::ForeignItem(foreign_item));
+        visit::walk_foreign_item(self, foreign_item);
+    }
+    fn visit_pat(&mut self, pat: &'a Pat) {
+        self.enter(Node::Pat(pat));
+        visit::walk_pat(self, pat);
+    }
+    fn visit_fn_decl(&mut self, fn_decl: &'a FnDecl) {
+        self.enter(Node::FnDecl(fn_decl));
+        visit::walk_fn_decl(self, fn_decl);
+    }
+    fn visit_trait_item(&mut self, trait_item: &'a TraitItem) {
+        self.enter(Node::TraitItem(trait_item));
+        visit::walk_trait_item(self, trait_item);
+    }
+    fn visit_impl_item(&mut self, impl_item: &'a ImplItem) {
+        self.enter(Node::ImplItem(impl_item));
+        visit::walk_impl_item(self, impl_item);
+    }
+    fn visit_block(&mut self, block: &'a Block) {
+        self.enter(Node::Block(block));
+        visit::walk_block(self, block);
+    }
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        self.enter(Node::Stmt(stmt));
+        visit::walk_stmt(self, stmt);
+    }
+    fn visit_local(&mut self, local: &'a Local) {
+        self.enter(Node::Local(local));
+        visit::walk_local(self, local);
+    }
+}
@@ -0,0 +1,168 @@
+//! Rewriting source text by replacing the text of individual nodes.
+
+use super::*;
+
+/// A replacement of the source text in `span` with `text`.
+///
+/// An empty `span` inserts `text` at `span.lo`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TextEdit {
+    pub span: Span,
+    pub text: String,
+}
+
+/// Collects replacements of nodes of a syntax tree and applies them to the
+/// source text the tree was parsed from as text edits, leaving the source text
+/// outside of the replaced nodes untouched.
+///
+/// ```rust,ignore
+/// let (krate, trivia) = try!(syn::parse_crate_lossless(&source));
+/// let mut rewriter = Rewriter::from(trivia);
+/// for item in &krate.items {
+///     if item.ident == "old_name" {
+///         let mut item = item.clone();
+///         item.ident = Ident::new("new_name");
+///         rewriter.replace(item.span, &item);
+///     }
+/// }
+/// let source = rewriter.apply();
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Rewriter {
+    trivia: Trivia,
+    /// Sorted by position, with insertions at the same offset kept in the
+    /// order they were made
+    edits: Vec<TextEdit>,
+}
+
+impl Rewriter {
+    /// Prepares to rewrite `source`, which must lex as a sequence of token
+    /// trees.
+    pub fn new(source: &str) -> Result<Self, ParseError> {
+        Ok(Rewriter::from(try!(Trivia::new(source))))
+    }
+
+    /// The source text being rewritten.
+    pub fn source(&self) -> &str {
+        self.trivia.source()
+    }
+
+    /// Replaces the source text in `span`, usually the span of the node being
+    /// replaced, with the printed `node`.
+    ///
+    /// The printed tokens which match a source token within `span` keep their
+    /// source text and the whitespace and comments before them, so only the
+    /// modified parts of the node are reformatted. See `Trivia::print_within`.
+    ///
+    /// The edit only covers the text which differs from the source, so
+    /// replacing a node with an unchanged copy makes no edit.
+    ///
+    /// Panics if `span` overlaps a span which was already replaced.
+    #[cfg(feature = "printing")]
+    pub fn replace<T: ::quote::ToTokens>(&mut self, span: Span, node: &T) {
+        let text = self.trivia.print_within(span, node);
+        let (prefix, suffix) = {
+            let old = &self.trivia.source()[span.lo..span.hi];
+            let prefix = common_prefix(old, &text);
+            (prefix, common_suffix(&old[prefix..], &text[prefix..]))
+        };
+        if text.len() == prefix + suffix && span.hi - span.lo == prefix + suffix {
+            return;
+        }
+        let changed = Span {
+            lo: span.lo + prefix,
+            hi: span.hi - suffix,
+        };
+        self.replace_text(changed, &text[prefix..text.len() - suffix]);
+    }
+
+    /// Replaces the source text in `span` with `text`.
+    ///
+    /// Panics if `span` is not within the source text or overlaps a span which
+    /// was already replaced.
+    pub fn replace_text<T: Into<String>>(&mut self, span: Span, text: T) {
+        let source = self.trivia.source();
+        if span.lo > span.hi || span.hi > source.len() || !source.is_char_boundary(span.lo) ||
+           !source.is_char_boundary(span.hi) {
+            panic!("span {:?} is not within the source text", span);
+        }
+        if let Some(edit) = self.edits.iter().find(|edit| overlaps(edit.span, span)) {
+            panic!("span {:?} overlaps the replaced span {:?}", span, edit.span);
+        }
+        let index = self.edits
+            .iter()
+            .position(|edit| (edit.span.lo, edit.span.hi) > (span.lo, span.hi))
+            .unwrap_or(self.edits.len());
+        self.edits.insert(index,
+                          TextEdit {
+                              span: span,
+                              text: text.into(),
+                          });
+    }
+
+    /// Removes the source text in `span`.
+    ///
+    /// Panics like `replace_text`.
+    pub fn remove(&mut self, span: Span) {
+        self.replace_text(span, "");
+    }
+
+    /// The edits made so far, in order of their position in the source text.
+    pub fn edits(&self) -> &[TextEdit] {
+        &self.edits
+    }
+
+    pub fn into_edits(self) -> Vec<TextEdit> {
+        self.edits
+    }
+
+    /// The source text with the edits applied.
+    pub fn apply(&self) -> String {
+        let source = self.trivia.source();
+        let mut out = String::with_capacity(source.len());
+        let mut pos = 0;
+        for edit in &self.edits {
+            out.push_str(&source[pos..edit.span.lo]);
+            out.push_str(&edit.text);
+            pos = edit.span.hi;
+        }
+        out.push_str(&source[pos..]);
+        out
+    }
+}
+
+impl From<Trivia> for Rewriter {
+    fn from(trivia: Trivia) -> Self {
+        Rewriter {
+            trivia: trivia,
+            edits: Vec::new(),
+        }
+    }
+}
+
+/// Whether replacing the text in both spans would be ambiguous. Insertions at
+/// either end of a replaced span are not.
+fn overlaps(a: Span, b: Span) -> bool {
+    a.lo < b.hi && b.lo < a.hi
+}
+
+/// The length in bytes of the longest common prefix of `a` and `b`.
+#[cfg(feature = "printing")]
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.chars()
+        .zip(b.chars())
+        .take_while(|&(x, y)| x == y)
+        .map(|(x, _)| x.len_utf8())
+        .sum()
+}
+
+/// The length in bytes of the longest common suffix of `a` and `b`.
+#[cfg(feature = "printing")]
+fn common_suffix(a: &str, b: &str) -> usize {
+    a.chars()
+        .rev()
+        .zip(b.chars().rev())
+        .take_while(|&(x, y)| x == y)
+        .map(|(x, _)| x.len_utf8())
+        .sum()
+}
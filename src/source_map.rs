@@ -0,0 +1,218 @@
+use Span;
+
+/// Identifies a file registered with a `SourceMap`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct FileId(usize);
+
+impl FileId {
+    /// The index of the file in the order it was added to its `SourceMap`.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A position within a source file.
+///
+/// Lines are 1-based. Columns are 0-based and are given in bytes, in chars and
+/// in UTF-16 code units from the start of the line.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Loc {
+    pub line: usize,
+    pub col: usize,
+    pub col_char: usize,
+    pub col_utf16: usize,
+}
+
+/// The resolved location of a `Span`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SpanLoc {
+    pub file: FileId,
+    pub lo: Loc,
+    pub hi: Loc,
+}
+
+/// A single source text registered with a `SourceMap`.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    id: FileId,
+    name: String,
+    src: String,
+    start: usize,
+    /// Byte offsets, relative to the file, of the start of each line
+    lines: Vec<usize>,
+}
+
+impl SourceFile {
+    fn new(id: FileId, name: String, src: String, start: usize) -> Self {
+        let mut lines = vec![0];
+        lines.extend(src.match_indices('\n').map(|(i, _)| i + 1));
+        SourceFile {
+            id: id,
+            name: name,
+            src: src,
+            start: start,
+            lines: lines,
+        }
+    }
+
+    pub fn id(&self) -> FileId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.src
+    }
+
+    /// The span covering the whole file. Spans of nodes parsed from this file
+    /// must fall within it.
+    pub fn span(&self) -> Span {
+        Span {
+            lo: self.start,
+            hi: self.start + self.src.len(),
+        }
+    }
+
+    /// Whether the span lies within this file.
+    pub fn contains(&self, span: Span) -> bool {
+        let file = self.span();
+        file.lo <= span.lo && span.lo <= span.hi && span.hi <= file.hi
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// The text of the given 1-based line, without its line terminator.
+    pub fn line(&self, line: usize) -> Option<&str> {
+        if line == 0 || line > self.lines.len() {
+            return None;
+        }
+        let lo = self.lines[line - 1];
+        let mut hi = self.lines.get(line).cloned().unwrap_or(self.src.len());
+        if self.src[lo..hi].ends_with('\n') {
+            hi -= 1;
+        }
+        if self.src[lo..hi].ends_with('\r') {
+            hi -= 1;
+        }
+        Some(&self.src[lo..hi])
+    }
+
+    /// Resolves an offset within this file to a line and column, or `None` if
+    /// the offset lies outside of the file.
+    pub fn lookup(&self, offset: usize) -> Option<Loc> {
+        if offset < self.start || offset > self.start + self.src.len() {
+            return None;
+        }
+        let offset = offset - self.start;
+        let line = match self.lines.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next) => next - 1,
+        };
+        let line_start = self.lines[line];
+        let mut loc = Loc {
+            line: line + 1,
+            col: offset - line_start,
+            col_char: 0,
+            col_utf16: 0,
+        };
+        for (i, ch) in self.src[line_start..].char_indices() {
+            if line_start + i >= offset {
+                break;
+            }
+            loc.col_char += 1;
+            loc.col_utf16 += ch.len_utf16();
+        }
+        Some(loc)
+    }
+
+    /// The source text covered by the span, or `None` if the span lies outside
+    /// of the file or does not fall on char boundaries.
+    pub fn span_text(&self, span: Span) -> Option<&str> {
+        if !self.contains(span) {
+            return None;
+        }
+        let (lo, hi) = (span.lo - self.start, span.hi - self.start);
+        if self.src.is_char_boundary(lo) && self.src.is_char_boundary(hi) {
+            Some(&self.src[lo..hi])
+        } else {
+            None
+        }
+    }
+}
+
+/// A collection of source files which resolves spans to file, line and
+/// column.
+///
+/// Each file is assigned a distinct range of offsets. The first file starts at
+/// offset 0, so spans produced by parsing it directly can be looked up as is;
+/// spans from later files must be offset by the start of their file's span.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        SourceMap::default()
+    }
+
+    /// Registers a source text, returning the id of the new file.
+    pub fn add_file<N, S>(&mut self, name: N, src: S) -> FileId
+        where N: Into<String>,
+              S: Into<String>
+    {
+        // Leave a gap of one between files so that an empty span at the end
+        // of one file is not mistaken for the start of the next.
+        let start = match self.files.last() {
+            Some(last) => last.span().hi + 1,
+            None => 0,
+        };
+        let id = FileId(self.files.len());
+        self.files.push(SourceFile::new(id, name.into(), src.into(), start));
+        id
+    }
+
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        &self.files[id.0]
+    }
+
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// The file containing the span, if any.
+    pub fn lookup_file(&self, span: Span) -> Option<&SourceFile> {
+        let index = match self.files.binary_search_by(|file| file.start.cmp(&span.lo)) {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(next) => next - 1,
+        };
+        let file = &self.files[index];
+        if file.contains(span) {
+            Some(file)
+        } else {
+            None
+        }
+    }
+
+    /// Resolves a span to its file and the line and column of both ends.
+    pub fn lookup(&self, span: Span) -> Option<SpanLoc> {
+        self.lookup_file(span).map(|file| {
+            SpanLoc {
+                file: file.id,
+                lo: file.lookup(span.lo).unwrap(),
+                hi: file.lookup(span.hi).unwrap(),
+            }
+        })
+    }
+
+    /// The source text covered by the span.
+    pub fn span_text(&self, span: Span) -> Option<&str> {
+        self.lookup_file(span).and_then(|file| file.span_text(span))
+    }
+}
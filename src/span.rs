@@ -1,6 +1,7 @@
+use std::cmp;
 use std::usize;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
 pub struct Span {
     pub lo: usize,
     pub hi: usize,
@@ -13,6 +14,14 @@ impl Span {
             hi: other.hi,
         }
     }
+
+    /// The smallest span covering both spans.
+    pub fn join(self, other: Span) -> Span {
+        Span {
+            lo: cmp::min(self.lo, other.lo),
+            hi: cmp::max(self.hi, other.hi),
+        }
+    }
 }
 
 pub const EMPTY_SPAN: Span = Span { lo: 0, hi: 0 };
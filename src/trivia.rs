@@ -0,0 +1,501 @@
+//! Whitespace and comments between tokens, for lossless printing.
+
+use super::*;
+use error;
+use mac::parsing::token_trees;
+use synom::{FurthestFailure, IResult, ParseState};
+use synom::space::skip_whitespace;
+
+/// The whitespace and comments surrounding the tokens of a source text.
+///
+/// The parser discards trivia, so printing a syntax tree with `ToTokens`
+/// normalizes its formatting. `Trivia` records the trivia of the text a tree
+/// was parsed from so that it can be restored when printing the tree.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Trivia {
+    source: String,
+    /// The length of the byte order mark and shebang line, if any
+    prefix: usize,
+    tokens: Vec<LexedToken>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct LexedToken {
+    span: Span,
+    /// The value of a literal token, which may be printed differently than it
+    /// was written
+    lit: Option<LitKind>,
+}
+
+impl Trivia {
+    /// Lexes `input`, recording the trivia before and after each token.
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let prefix = prefix_len(input);
+        Ok(Trivia {
+            source: input.to_owned(),
+            prefix: prefix,
+            tokens: try!(lex(input, prefix)),
+        })
+    }
+
+    /// The source text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The whitespace and comments before the token starting at `span.lo`, or
+    /// `None` if no token starts there.
+    ///
+    /// Passing the span of a node gives the trivia before the node.
+    pub fn leading(&self, span: Span) -> Option<&str> {
+        match self.tokens.binary_search_by_key(&span.lo, |token| token.span.lo) {
+            Ok(index) => Some(&self.source[self.gap_start(index)..span.lo]),
+            Err(_) => None,
+        }
+    }
+
+    /// The whitespace and comments after the token ending at `span.hi`, or
+    /// `None` if no token ends there.
+    ///
+    /// Passing the span of a node gives the trivia after the node.
+    pub fn trailing(&self, span: Span) -> Option<&str> {
+        match self.tokens.binary_search_by_key(&span.hi, |token| token.span.hi) {
+            Ok(index) => Some(&self.source[span.hi..self.gap_end(index + 1)]),
+            Err(_) => None,
+        }
+    }
+
+    fn gap_start(&self, index: usize) -> usize {
+        if index == 0 {
+            self.prefix
+        } else {
+            self.tokens[index - 1].span.hi
+        }
+    }
+
+    fn gap_end(&self, index: usize) -> usize {
+        match self.tokens.get(index) {
+            Some(token) => token.span.lo,
+            None => self.source.len(),
+        }
+    }
+}
+
+/// The length of the byte order mark and shebang line at the start of
+/// `input`, which are not tokens.
+fn prefix_len(input: &str) -> usize {
+    let bom = bom_len(input);
+    let rest = &input[bom..];
+    if rest.starts_with("#!") && !rest[2..].starts_with('[') {
+        bom + rest.find('\n').unwrap_or(rest.len())
+    } else {
+        bom
+    }
+}
+
+fn bom_len(input: &str) -> usize {
+    if input.starts_with('\u{feff}') {
+        '\u{feff}'.len_utf8()
+    } else {
+        0
+    }
+}
+
+fn lex(input: &str, prefix: usize) -> Result<Vec<LexedToken>, ParseError> {
+    let failure = FurthestFailure::new();
+    let start = ParseState::tracking(input, &failure).advance(prefix);
+    match token_trees(start) {
+        IResult::Done(rest, tts) => {
+            if skip_whitespace(rest).is_empty() {
+                let mut tokens = Vec::new();
+                flatten(tts, &mut tokens);
+                Ok(tokens)
+            } else {
+                Err(error::from_failure("token trees", input, start, Some(rest), &failure))
+            }
+        }
+        IResult::Error => Err(error::from_failure("token trees", input, start, None, &failure)),
+    }
+}
+
+fn flatten(tts: Vec<TokenTree>, tokens: &mut Vec<LexedToken>) {
+    for tt in tts {
+        match tt {
+            TokenTree::Token(Token::Literal(lit), span) => {
+                tokens.push(LexedToken {
+                    span: span,
+                    lit: Some(lit.node),
+                });
+            }
+            TokenTree::Token(Token::Ident(_), span) |
+            TokenTree::Token(Token::Underscore, span) |
+            TokenTree::Token(Token::Lifetime(_), span) |
+            TokenTree::Token(Token::DocComment(_), span) => {
+                tokens.push(LexedToken {
+                    span: span,
+                    lit: None,
+                });
+            }
+            TokenTree::Token(_, span) => {
+                // Punctuation is split into single characters, as e.g. `>>`
+                // may be printed as `> >`
+                for lo in span.lo..span.hi {
+                    tokens.push(LexedToken {
+                        span: Span {
+                            lo: lo,
+                            hi: lo + 1,
+                        },
+                        lit: None,
+                    });
+                }
+            }
+            TokenTree::Delimited(delimited, span) => {
+                tokens.push(LexedToken {
+                    span: Span {
+                        lo: span.lo,
+                        hi: span.lo + 1,
+                    },
+                    lit: None,
+                });
+                flatten(delimited.tts, tokens);
+                tokens.push(LexedToken {
+                    span: Span {
+                        lo: span.hi - 1,
+                        hi: span.hi,
+                    },
+                    lit: None,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use super::*;
+    use quote::{Tokens, ToTokens};
+    use std::cmp;
+    use std::ops::Range;
+
+    /// The longest edit script `diff` searches for before falling back to
+    /// matching the unchanged tokens at the start and end.
+    const MAX_EDITS: usize = 1000;
+
+    #[derive(PartialEq)]
+    enum Key<'a> {
+        Text(&'a str),
+        Lit(&'a LitKind),
+    }
+
+    fn keys<'a>(source: &'a str, tokens: &'a [LexedToken]) -> Vec<Key<'a>> {
+        tokens.iter()
+            .map(|token| match token.lit {
+                Some(ref lit) => Key::Lit(lit),
+                None => Key::Text(&source[token.span.lo..token.span.hi]),
+            })
+            .collect()
+    }
+
+    impl Trivia {
+        /// Prints `node`, restoring the source text and trivia of every
+        /// printed token which matches a token of the source.
+        ///
+        /// Tokens are matched by diffing the printed tokens against the source
+        /// tokens, so an unmodified `Crate` prints as its source text and the
+        /// formatting and comments of the unchanged parts of a modified tree
+        /// are kept. Printed tokens without a match are spaced as `ToTokens`
+        /// spaced them.
+        pub fn print<T: ToTokens>(&self, node: &T) -> String {
+            self.print_tokens(0..self.tokens.len(), true, node)
+        }
+
+        /// Prints `node` as a replacement for the source text in `span`,
+        /// restoring the source text and trivia of every printed token which
+        /// matches a token within `span`.
+        ///
+        /// Unlike `print`, the trivia before and after `span` is never
+        /// included, so the result can be spliced in place of the text in
+        /// `span`.
+        pub fn print_within<T: ToTokens>(&self, span: Span, node: &T) -> String {
+            let start = self.token_index(span.lo);
+            let end = self.token_index(span.hi);
+            self.print_tokens(start..end, false, node)
+        }
+
+        /// Prints `node` against the source tokens in `range`. The trivia
+        /// before and after the range is included if `outer` is set and the
+        /// printed tokens start and end with the first and last tokens of the
+        /// range.
+        fn print_tokens<T: ToTokens>(&self, range: Range<usize>, outer: bool, node: &T) -> String {
+            let mut tokens = Tokens::new();
+            node.to_tokens(&mut tokens);
+            let printed = tokens.as_str();
+            let prefix = prefix_len(printed);
+            let new = match lex(printed, prefix) {
+                Ok(new) => new,
+                Err(_) => return printed.to_owned(),
+            };
+            let within = &self.tokens[range.clone()];
+            let matches: Vec<_> = diff(&keys(&self.source, within), &keys(printed, &new))
+                .into_iter()
+                .map(|old| old.map(|old| old + range.start))
+                .collect();
+
+            // The trivia before and after a node printed on its own belongs to
+            // its parent
+            let whole = outer &&
+                        matches.first().and_then(|&first| first).unwrap_or(0) == range.start;
+
+            let mut out = String::new();
+            let bom = bom_len(&self.source);
+            if whole && printed[..prefix] == self.source[bom..self.prefix] {
+                out.push_str(&self.source[..self.prefix]);
+            } else {
+                out.push_str(&printed[..prefix]);
+            }
+
+            // The source token matching the previous printed token, and the
+            // last source token matched
+            let mut prev: Option<usize> = None;
+            let mut last: Option<usize> = None;
+            let mut after_line_comment = false;
+            for (index, token) in new.iter().enumerate() {
+                // Leave out a trailing comma which the source didn't have
+                if let Some(prev) = prev {
+                    if matches[index].is_none() && &printed[token.span.lo..token.span.hi] == "," &&
+                       matches.get(index + 1) == Some(&Some(prev + 1)) &&
+                       is_close(self.text(prev + 1)) {
+                        continue;
+                    }
+                }
+                let gap_start = if index == 0 { prefix } else { new[index - 1].span.hi };
+                let gap = &printed[gap_start..token.span.lo];
+                let (leading, text) = match matches[index] {
+                    Some(old) => {
+                        let start = match prev {
+                            // Keep a comma which the parser dropped, e.g. a
+                            // trailing comma or one ending a `where` clause
+                            Some(prev) if prev + 2 == old && self.text(prev + 1) == "," => {
+                                self.gap_start(prev + 1)
+                            }
+                            _ => self.gap_start(old),
+                        };
+                        let mut leading = &self.source[start..self.tokens[old].span.lo];
+                        if !whole && (index == 0 || old == range.start) {
+                            // The trivia before a node printed on its own
+                            // belongs to its parent
+                            leading = "";
+                        } else if prev.is_none() && last.map(|last| last + 1) == Some(old) &&
+                                  !out.ends_with('}') && !out.ends_with(';') {
+                            // The tokens inserted before this one took its
+                            // leading whitespace, unless they end a statement
+                            // or item
+                            leading = &leading[whitespace_len(leading)..];
+                        }
+                        let text = self.text(old);
+                        let adjacent = prev.map(|prev| prev + 1) == Some(old);
+                        prev = Some(old);
+                        last = Some(old);
+                        if index == 0 || adjacent || !leading.is_empty() ||
+                           !separate(&out, text) {
+                            (leading, text)
+                        } else {
+                            (gap, text)
+                        }
+                    }
+                    None => {
+                        let leading = match prev {
+                            // The first of a run of new tokens takes the place
+                            // of the source tokens it replaces, or is inserted
+                            // before the next source token
+                            Some(prev) if prev + 1 < range.end => {
+                                let next = &self.source[self.gap_start(prev + 1)..
+                                                        self.tokens[prev + 1].span.lo];
+                                let next_match = matches[index..].iter().filter_map(|&m| m).next();
+                                if next_match == Some(prev + 1) {
+                                    &next[..whitespace_len(next)]
+                                } else {
+                                    next
+                                }
+                            }
+                            _ => gap,
+                        };
+                        prev = None;
+                        (leading, &printed[token.span.lo..token.span.hi])
+                    }
+                };
+                if after_line_comment && !starts_line(leading) {
+                    out.push('\n');
+                }
+                out.push_str(leading);
+                out.push_str(text);
+                after_line_comment = text.starts_with("//");
+            }
+
+            match prev {
+                Some(old) if whole && old + 1 == range.end => {
+                    out.push_str(&self.source[self.gap_start(old + 1)..]);
+                }
+                _ => {
+                    let end = new.last().map_or(prefix, |token| token.span.hi);
+                    out.push_str(&printed[end..]);
+                }
+            }
+            out
+        }
+
+        /// The index of the first token starting at or after `offset`.
+        fn token_index(&self, offset: usize) -> usize {
+            match self.tokens.binary_search_by_key(&offset, |token| token.span.lo) {
+                Ok(index) | Err(index) => index,
+            }
+        }
+
+        fn text(&self, index: usize) -> &str {
+            let span = self.tokens[index].span;
+            &self.source[span.lo..span.hi]
+        }
+    }
+
+    /// The length of the whitespace before the first comment in `leading`.
+    fn whitespace_len(leading: &str) -> usize {
+        leading.find(|ch: char| !ch.is_whitespace()).unwrap_or(leading.len())
+    }
+
+    /// Whether `leading` ends the line of a preceding line comment.
+    fn starts_line(leading: &str) -> bool {
+        match leading.find('\n') {
+            Some(end) => leading[..end].trim().is_empty(),
+            None => false,
+        }
+    }
+
+    /// Whether tokens ending with `before` and starting with `after` need to
+    /// be separated to lex as two tokens, conservatively.
+    fn separate(before: &str, after: &str) -> bool {
+        fn class(ch: Option<char>) -> u8 {
+            match ch {
+                Some('(') | Some(')') | Some('[') | Some(']') | Some('{') | Some('}') |
+                Some(',') | Some(';') | None => 0,
+                Some(ch) if ch.is_alphanumeric() || "_'\".".contains(ch) => 1,
+                Some(_) => 2,
+            }
+        }
+        let before = class(before.chars().next_back());
+        before != 0 && before == class(after.chars().next())
+    }
+
+    fn is_close(text: &str) -> bool {
+        match text {
+            ")" | "]" | "}" | ">" => true,
+            _ => false,
+        }
+    }
+
+    /// Matches the tokens of `new` to the tokens of `old` along the longest
+    /// common subsequence, giving the index of the matching old token for each
+    /// new token.
+    ///
+    /// If the tokens differ by more than `MAX_EDITS` edits, only their common
+    /// prefix and suffix are matched.
+    fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Option<usize>> {
+        let mut matches = vec![None; new.len()];
+        match myers(old, new) {
+            Some(pairs) => {
+                for (i, j) in pairs {
+                    matches[j] = Some(i);
+                }
+            }
+            None => {
+                let prefix = old.iter().zip(new).take_while(|&(a, b)| a == b).count();
+                for (j, m) in matches[..prefix].iter_mut().enumerate() {
+                    *m = Some(j);
+                }
+                let suffix = old[prefix..]
+                    .iter()
+                    .rev()
+                    .zip(new[prefix..].iter().rev())
+                    .take_while(|&(a, b)| a == b)
+                    .count();
+                for k in 0..suffix {
+                    matches[new.len() - 1 - k] = Some(old.len() - 1 - k);
+                }
+            }
+        }
+        matches
+    }
+
+    /// Myers' O(ND) difference algorithm, giving the pairs of indices of
+    /// matching elements, or `None` if the shortest edit script is longer than
+    /// `MAX_EDITS`.
+    fn myers<T: PartialEq>(a: &[T], b: &[T]) -> Option<Vec<(usize, usize)>> {
+        let n = a.len() as isize;
+        let m = b.len() as isize;
+        if n == 0 || m == 0 {
+            return Some(Vec::new());
+        }
+        let max = cmp::min(a.len() + b.len(), MAX_EDITS) as isize;
+        let index = |k: isize| (k + max + 1) as usize;
+
+        // `v[index(k)]` is the furthest `x` reached on diagonal `k`
+        let mut v = vec![0; index(max + 1) + 1];
+        let mut trace = Vec::new();
+        for d in 0..max + 1 {
+            trace.push(v.clone());
+            let mut k = -d;
+            while k <= d {
+                let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                    v[index(k + 1)]
+                } else {
+                    v[index(k - 1)] + 1
+                };
+                let mut y = x - k;
+                while x < n && y < m && a[x as usize] == b[y as usize] {
+                    x += 1;
+                    y += 1;
+                }
+                v[index(k)] = x;
+                if x >= n && y >= m {
+                    return Some(backtrack(&trace, index, x, y, a, b));
+                }
+                k += 2;
+            }
+        }
+        None
+    }
+
+    fn backtrack<T, F>(trace: &[Vec<isize>],
+                       index: F,
+                       mut x: isize,
+                       mut y: isize,
+                       a: &[T],
+                       b: &[T])
+                       -> Vec<(usize, usize)>
+        where T: PartialEq,
+              F: Fn(isize) -> usize
+    {
+        let mut pairs = Vec::new();
+        for (d, v) in trace.iter().enumerate().rev() {
+            let d = d as isize;
+            let k = x - y;
+            let prev_k = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                k + 1
+            } else {
+                k - 1
+            };
+            let prev_x = v[index(prev_k)];
+            let prev_y = prev_x - prev_k;
+            while x > prev_x && y > prev_y {
+                x -= 1;
+                y -= 1;
+                let (i, j) = (x as usize, y as usize);
+                if i < a.len() && j < b.len() && a[i] == b[j] {
+                    pairs.push((i, j));
+                }
+            }
+            x = prev_x;
+            y = prev_y;
+        }
+        pairs
+    }
+}
@@ -1,8 +1,35 @@
 use super::*;
 
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Ty {
+    pub node: TyKind,
+    pub span: Span,
+}
+
+impl Ty {
+    pub fn new(node: TyKind, span: Span) -> Ty {
+        Ty {
+            node: node,
+            span: span,
+        }
+    }
+}
+
+impl From<Spanned<TyKind>> for Ty {
+    fn from(spanned: Spanned<TyKind>) -> Ty {
+        Ty::new(spanned.node, spanned.span)
+    }
+}
+
+impl From<TyKind> for Ty {
+    fn from(node: TyKind) -> Ty {
+        Ty::new(node, EMPTY_SPAN)
+    }
+}
+
 /// The different kinds of types recognized by the compiler
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub enum Ty {
+pub enum TyKind {
     /// A variable-length array (`[T]`)
     Slice(Box<Ty>),
     /// A fixed length array (`[T; n]`)
@@ -22,9 +49,9 @@ pub enum Ty {
     ///
     /// Type parameters are stored in the Path itself
     Path(Option<QSelf>, Path),
-    /// A trait object type `Bound1 + Bound2 + Bound3`
-    /// where `Bound` is a trait or a lifetime.
-    TraitObject(Vec<TyParamBound>),
+    /// A trait object type `Bound1 + Bound2 + Bound3` or
+    /// `dyn Bound1 + Bound2 + Bound3` where `Bound` is a trait or a lifetime.
+    TraitObject(Vec<TyParamBound>, TraitObjectSyntax),
     /// An `impl Bound1 + Bound2 + Bound3` type
     /// where `Bound` is a trait or a lifetime.
     ImplTrait(Vec<TyParamBound>),
@@ -37,6 +64,21 @@ pub enum Ty {
     Mac(Mac),
 }
 
+impl TyKind {
+    pub fn span(self, span: Span) -> Ty {
+        Ty::new(self, span)
+    }
+}
+
+/// Whether a trait object type was written with the `dyn` keyword.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TraitObjectSyntax {
+    /// `dyn Trait`
+    Dyn,
+    /// A bare `Trait`
+    None,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct MutTy {
     pub ty: Ty,
@@ -62,6 +104,9 @@ pub struct Path {
     pub global: bool,
     /// The segments in the path: the things separated by `::`.
     pub segments: Vec<PathSegment>,
+    /// For a qualified path, this covers the entire `<T as Trait>::Item`
+    /// including the qualified self type.
+    pub span: Span,
 }
 
 impl<T> From<T> for Path
@@ -71,6 +116,7 @@ impl<T> From<T> for Path
         Path {
             global: false,
             segments: vec![segment.into()],
+            span: EMPTY_SPAN,
         }
     }
 }
@@ -88,6 +134,7 @@ pub struct PathSegment {
     /// parens affects the region binding rules, so we preserve the
     /// distinction.
     pub parameters: PathParameters,
+    pub span: Span,
 }
 
 impl<T> From<T> for PathSegment
@@ -97,6 +144,7 @@ impl<T> From<T> for PathSegment
         PathSegment {
             ident: ident.into(),
             parameters: PathParameters::none(),
+            span: EMPTY_SPAN,
         }
     }
 }
@@ -120,7 +168,7 @@ impl PathParameters {
     pub fn is_empty(&self) -> bool {
         match *self {
             PathParameters::AngleBracketed(ref bracketed) => {
-                bracketed.lifetimes.is_empty() && bracketed.types.is_empty() &&
+                bracketed.lifetimes.is_empty() && bracketed.args.is_empty() &&
                 bracketed.bindings.is_empty()
             }
             PathParameters::Parenthesized(_) => false,
@@ -133,12 +181,25 @@ impl PathParameters {
 pub struct AngleBracketedParameterData {
     /// The lifetime parameters for this path segment.
     pub lifetimes: Vec<Lifetime>,
-    /// The type parameters for this path segment, if present.
-    pub types: Vec<Ty>,
+    /// The type and const parameters for this path segment in the order they
+    /// were written, if present.
+    pub args: Vec<GenericArg>,
     /// Bindings (equality constraints) on associated types, if present.
     ///
     /// E.g., `Foo<A=Bar>`.
     pub bindings: Vec<TypeBinding>,
+    /// The span from `<` to `>`, or an empty span just after the identifier
+    /// if the segment has no angle brackets.
+    pub span: Span,
+}
+
+/// A type or const argument of a path segment.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum GenericArg {
+    Type(Ty),
+    /// E.g., `3` or `{ N + 1 }`. A const argument which is a plain path, like
+    /// the `N` in `Foo<N>`, can not be told apart from a type and is a `Type`.
+    Const(ConstExpr),
 }
 
 /// Bind a type to an associated type: `A=Foo`.
@@ -183,6 +244,8 @@ pub struct PolyTraitRef {
 pub struct QSelf {
     pub ty: Box<Ty>,
     pub position: usize,
+    /// The span from `<` to `>`
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -192,6 +255,8 @@ pub struct BareFnTy {
     pub lifetimes: Vec<LifetimeDef>,
     pub inputs: Vec<BareFnArg>,
     pub output: FunctionRetTy,
+    /// The span of the `->` before an explicit return type
+    pub rarrow_span: Option<Span>,
     pub variadic: bool,
 }
 
@@ -234,7 +299,7 @@ pub mod parsing {
     use {TyParamBound, TraitBoundModifier};
     #[cfg(feature = "full")]
     use ConstExpr;
-    use constant::parsing::const_expr;
+    use constant::parsing::{const_arg, const_expr};
     #[cfg(feature = "full")]
     use expr::parsing::expr;
     use generics::parsing::{lifetime, lifetime_def, ty_param_bound, bound_lifetimes};
@@ -242,11 +307,13 @@ pub mod parsing {
     use lit::parsing::quoted_string;
     use mac::parsing::mac;
 
-    named!(pub ty -> Ty, alt!(
+    named!(pub ty -> Ty, label!("type", map!(spanned!(alt!(
         ty_paren // must be before ty_tup
         |
         ty_mac // must be before ty_path
         |
+        ty_dyn_trait // must be before ty_path
+        |
         ty_path // must be before ty_poly_trait_ref
         |
         ty_vec
@@ -266,29 +333,29 @@ pub mod parsing {
         ty_poly_trait_ref
         |
         ty_impl_trait
-    ));
+    )), <Ty as From<Spanned<TyKind>>>::from)));
 
-    named!(ty_mac -> Ty, map!(mac, Ty::Mac));
+    named!(ty_mac -> TyKind, map!(mac, TyKind::Mac));
 
-    named!(ty_vec -> Ty, do_parse!(
+    named!(ty_vec -> TyKind, do_parse!(
         punct!("[") >>
         elem: ty >>
         punct!("]") >>
-        (Ty::Slice(Box::new(elem)))
+        (TyKind::Slice(Box::new(elem)))
     ));
 
     #[cfg(not(feature = "full"))]
-    named!(ty_array -> Ty, do_parse!(
+    named!(ty_array -> TyKind, do_parse!(
         punct!("[") >>
         elem: ty >>
         punct!(";") >>
         len: const_expr >>
         punct!("]") >>
-        (Ty::Array(Box::new(elem), len))
+        (TyKind::Array(Box::new(elem), len))
     ));
 
     #[cfg(feature = "full")]
-    named!(ty_array -> Ty, do_parse!(
+    named!(ty_array -> TyKind, do_parse!(
         punct!("[") >>
         elem: ty >>
         punct!(";") >>
@@ -297,10 +364,10 @@ pub mod parsing {
             |
             terminated!(expr, punct!("]")) => { ConstExpr::Other }
         ) >>
-        (Ty::Array(Box::new(elem), len))
+        (TyKind::Array(Box::new(elem), len))
     ));
 
-    named!(ty_ptr -> Ty, do_parse!(
+    named!(ty_ptr -> TyKind, do_parse!(
         punct!("*") >>
         mutability: alt!(
             keyword!("const") => { |_| Mutability::Immutable }
@@ -308,24 +375,24 @@ pub mod parsing {
             keyword!("mut") => { |_| Mutability::Mutable }
         ) >>
         target: ty >>
-        (Ty::Ptr(Box::new(MutTy {
+        (TyKind::Ptr(Box::new(MutTy {
             ty: target,
             mutability: mutability,
         })))
     ));
 
-    named!(ty_rptr -> Ty, do_parse!(
+    named!(ty_rptr -> TyKind, do_parse!(
         punct!("&") >>
         life: option!(lifetime) >>
         mutability: mutability >>
         target: ty >>
-        (Ty::Rptr(life, Box::new(MutTy {
+        (TyKind::Rptr(life, Box::new(MutTy {
             ty: target,
             mutability: mutability,
         })))
     ));
 
-    named!(ty_bare_fn -> Ty, do_parse!(
+    named!(ty_bare_fn -> TyKind, do_parse!(
         lifetimes: opt_vec!(do_parse!(
             keyword!("for") >>
             punct!("<") >>
@@ -341,46 +408,47 @@ pub mod parsing {
         trailing_comma: option!(punct!(",")) >>
         variadic: option!(cond_reduce!(trailing_comma.is_some(), punct!("..."))) >>
         punct!(")") >>
-        output: option!(preceded!(
-            punct!("->"),
+        output: option!(tuple!(
+            spanned!(punct!("->")),
             ty
         )) >>
-        (Ty::BareFn(Box::new(BareFnTy {
+        (TyKind::BareFn(Box::new(BareFnTy {
             unsafety: unsafety,
             abi: abi,
             lifetimes: lifetimes,
             inputs: inputs,
+            rarrow_span: output.as_ref().map(|output| output.0.span),
             output: match output {
-                Some(ty) => FunctionRetTy::Ty(ty),
+                Some((_, ty)) => FunctionRetTy::Ty(ty),
                 None => FunctionRetTy::Default,
             },
             variadic: variadic.is_some(),
         })))
     ));
 
-    named!(ty_never -> Ty, map!(punct!("!"), |_| Ty::Never));
+    named!(ty_never -> TyKind, map!(punct!("!"), |_| TyKind::Never));
 
-    named!(ty_tup -> Ty, do_parse!(
+    named!(ty_tup -> TyKind, do_parse!(
         punct!("(") >>
         elems: terminated_list!(punct!(","), ty) >>
         punct!(")") >>
-        (Ty::Tup(elems))
+        (TyKind::Tup(elems))
     ));
 
-    named!(ty_path -> Ty, do_parse!(
+    named!(ty_path -> TyKind, do_parse!(
         qpath: qpath >>
         parenthesized: cond!(
-            qpath.1.segments.last().unwrap().parameters == PathParameters::none(),
-            option!(parenthesized_parameter_data)
+            qpath.1.segments.last().unwrap().parameters.is_empty(),
+            option!(spanned!(parenthesized_parameter_data))
         ) >>
         bounds: many0!(preceded!(punct!("+"), ty_param_bound)) >>
         ({
             let (qself, mut path) = qpath;
             if let Some(Some(parenthesized)) = parenthesized {
-                path.segments.last_mut().unwrap().parameters = parenthesized;
+                set_parenthesized(&mut path, parenthesized);
             }
             if bounds.is_empty() {
-                Ty::Path(qself, path)
+                TyKind::Path(qself, path)
             } else {
                 let path = TyParamBound::Trait(
                     PolyTraitRef {
@@ -390,7 +458,7 @@ pub mod parsing {
                     TraitBoundModifier::None,
                 );
                 let bounds = Some(path).into_iter().chain(bounds).collect();
-                Ty::TraitObject(bounds)
+                TyKind::TraitObject(bounds, TraitObjectSyntax::None)
             }
         })
     ));
@@ -414,52 +482,76 @@ pub mod parsing {
     named!(pub qpath -> (Option<QSelf>, Path), alt!(
         map!(path, |p| (None, p))
         |
-        do_parse!(
-            punct!("<") >>
-            this: map!(ty, Box::new) >>
-            path: option!(preceded!(
-                keyword!("as"),
-                path
+        map!(spanned!(do_parse!(
+            qself: spanned!(do_parse!(
+                punct!("<") >>
+                this: map!(ty, Box::new) >>
+                path: option!(preceded!(
+                    keyword!("as"),
+                    path
+                )) >>
+                punct!(">") >>
+                (this, path)
             )) >>
-            punct!(">") >>
             punct!("::") >>
             rest: separated_nonempty_list!(punct!("::"), path_segment) >>
             ({
+                let Spanned { node: (this, path), span } = qself;
                 match path {
                     Some(mut path) => {
                         let pos = path.segments.len();
                         path.segments.extend(rest);
-                        (Some(QSelf { ty: this, position: pos }), path)
+                        (QSelf { ty: this, position: pos, span: span }, path)
                     }
                     None => {
-                        (Some(QSelf { ty: this, position: 0 }), Path {
+                        (QSelf { ty: this, position: 0, span: span }, Path {
                             global: false,
                             segments: rest,
+                            span: DUMMY_SPAN,
                         })
                     }
                 }
             })
-        )
+        )), |Spanned { node: (qself, mut path), span }: Spanned<(QSelf, Path)>| {
+            path.span = span;
+            (Some(qself), path)
+        })
         |
-        map!(keyword!("self"), |_| (None, "self".into()))
+        map!(spanned!(keyword!("self")), |s: Spanned<&str>| {
+            (None, Path {
+                global: false,
+                segments: vec![segment_without_parameters(Ident::with_span("self", s.span),
+                                                          s.span)],
+                span: s.span,
+            })
+        })
     ));
 
-    named!(ty_poly_trait_ref -> Ty, map!(
+    named!(ty_poly_trait_ref -> TyKind, map!(
         separated_nonempty_list!(punct!("+"), ty_param_bound),
-        Ty::TraitObject
+        |bounds| TyKind::TraitObject(bounds, TraitObjectSyntax::None)
+    ));
+
+    named!(ty_dyn_trait -> TyKind, do_parse!(
+        keyword!("dyn") >>
+        // `dyn` is not a keyword in a path such as `dyn::Trait` or `dyn<T>`
+        not!(punct!("::")) >>
+        not!(punct!("<")) >>
+        bounds: separated_nonempty_list!(punct!("+"), ty_param_bound) >>
+        (TyKind::TraitObject(bounds, TraitObjectSyntax::Dyn))
     ));
 
-    named!(ty_impl_trait -> Ty, do_parse!(
+    named!(ty_impl_trait -> TyKind, do_parse!(
         keyword!("impl") >>
         elem: separated_nonempty_list!(punct!("+"), ty_param_bound) >>
-        (Ty::ImplTrait(elem))
+        (TyKind::ImplTrait(elem))
     ));
 
-    named!(ty_paren -> Ty, do_parse!(
+    named!(ty_paren -> TyKind, do_parse!(
         punct!("(") >>
         elem: ty >>
         punct!(")") >>
-        (Ty::Paren(Box::new(elem)))
+        (TyKind::Paren(Box::new(elem)))
     ));
 
     named!(pub mutability -> Mutability, alt!(
@@ -468,54 +560,99 @@ pub mod parsing {
         epsilon!() => { |_| Mutability::Immutable }
     ));
 
-    named!(pub path -> Path, do_parse!(
+    named!(pub path -> Path, map!(spanned!(do_parse!(
         global: option!(punct!("::")) >>
         segments: separated_nonempty_list!(punct!("::"), path_segment) >>
-        (Path {
-            global: global.is_some(),
-            segments: segments,
-        })
-    ));
-
-    named!(path_segment -> PathSegment, alt!(
+        (global.is_some(), segments)
+    )), |Spanned { node: (global, segments), span }| Path {
+        global: global,
+        segments: segments,
+        span: span,
+    }));
+
+    named!(path_segment -> PathSegment, add_span!(alt!(
         do_parse!(
             id: option!(ident) >>
-            punct!("<") >>
-            lifetimes: separated_list!(punct!(","), lifetime) >>
-            types: opt_vec!(preceded!(
-                cond!(!lifetimes.is_empty(), punct!(",")),
-                separated_nonempty_list!(
-                    punct!(","),
-                    terminated!(ty, not!(punct!("=")))
-                )
-            )) >>
-            bindings: opt_vec!(preceded!(
-                cond!(!lifetimes.is_empty() || !types.is_empty(), punct!(",")),
-                separated_nonempty_list!(punct!(","), type_binding)
+            data: spanned!(do_parse!(
+                punct!("<") >>
+                lifetimes: separated_list!(punct!(","), lifetime) >>
+                args: opt_vec!(preceded!(
+                    cond!(!lifetimes.is_empty(), punct!(",")),
+                    separated_nonempty_list!(punct!(","), generic_arg)
+                )) >>
+                bindings: opt_vec!(preceded!(
+                    cond!(!lifetimes.is_empty() || !args.is_empty(), punct!(",")),
+                    separated_nonempty_list!(punct!(","), type_binding)
+                )) >>
+                cond!(!lifetimes.is_empty() || !args.is_empty() || !bindings.is_empty(),
+                      option!(punct!(","))) >>
+                punct!(">") >>
+                (lifetimes, args, bindings)
             )) >>
-            cond!(!lifetimes.is_empty() || !types.is_empty() || !bindings.is_empty(), option!(punct!(","))) >>
-            punct!(">") >>
             (PathSegment {
                 ident: id.unwrap_or_else(|| "".into()),
                 parameters: PathParameters::AngleBracketed(
                     AngleBracketedParameterData {
-                        lifetimes: lifetimes,
-                        types: types,
-                        bindings: bindings,
+                        lifetimes: data.node.0,
+                        args: data.node.1,
+                        bindings: data.node.2,
+                        span: data.span,
                     }
                 ),
+                span: DUMMY_SPAN,
             })
         )
         |
-        map!(ident, Into::into)
+        map!(spanned!(ident), |id: Spanned<Ident>| {
+            segment_without_parameters(id.node, id.span)
+        })
         |
-        map!(alt!(
+        map!(spanned!(alt!(
             keyword!("super")
             |
             keyword!("self")
             |
             keyword!("Self")
-        ), Into::into)
+        )), |kw: Spanned<&str>| {
+            segment_without_parameters(Ident::with_span(kw.node, kw.span), kw.span)
+        })
+    )));
+
+    /// A segment with no parameters, whose empty parameter list is located
+    /// just after the identifier.
+    fn segment_without_parameters(ident: Ident, span: Span) -> PathSegment {
+        PathSegment {
+            ident: ident,
+            parameters: PathParameters::AngleBracketed(AngleBracketedParameterData {
+                span: Span {
+                    lo: span.hi,
+                    hi: span.hi,
+                },
+                ..AngleBracketedParameterData::default()
+            }),
+            span: DUMMY_SPAN,
+        }
+    }
+
+    /// Attach parenthesized parameters to the last segment of a path,
+    /// extending the spans of the segment and the path to cover them.
+    fn set_parenthesized(path: &mut Path, parenthesized: Spanned<PathParameters>) {
+        path.span = path.span.extend(parenthesized.span);
+        let segment = path.segments.last_mut().unwrap();
+        segment.span = segment.span.extend(parenthesized.span);
+        segment.parameters = parenthesized.node;
+    }
+
+    named!(generic_arg -> GenericArg, alt!(
+        do_parse!(
+            ty: ty >>
+            not!(punct!("=")) >>
+            // not the prefix of a literal, e.g. the b of b'x'
+            not!(alt!(tag!("'") | tag!("\"") | tag!("#"))) >>
+            (ty)
+        ) => { GenericArg::Type }
+        |
+        const_arg => { GenericArg::Const }
     ));
 
     named!(type_binding -> TypeBinding, do_parse!(
@@ -532,13 +669,13 @@ pub mod parsing {
         bound_lifetimes: bound_lifetimes >>
         trait_ref: path >>
         parenthesized: option!(cond_reduce!(
-            trait_ref.segments.last().unwrap().parameters == PathParameters::none(),
-            parenthesized_parameter_data
+            trait_ref.segments.last().unwrap().parameters.is_empty(),
+            spanned!(parenthesized_parameter_data)
         )) >>
         ({
             let mut trait_ref = trait_ref;
             if let Some(parenthesized) = parenthesized {
-                trait_ref.segments.last_mut().unwrap().parameters = parenthesized;
+                set_parenthesized(&mut trait_ref, parenthesized);
             }
             PolyTraitRef {
                 bound_lifetimes: bound_lifetimes,
@@ -584,20 +721,20 @@ mod printing {
 
     impl ToTokens for Ty {
         fn to_tokens(&self, tokens: &mut Tokens) {
-            match *self {
-                Ty::Slice(ref inner) => {
+            match self.node {
+                TyKind::Slice(ref inner) => {
                     tokens.append("[");
                     inner.to_tokens(tokens);
                     tokens.append("]");
                 }
-                Ty::Array(ref inner, ref len) => {
+                TyKind::Array(ref inner, ref len) => {
                     tokens.append("[");
                     inner.to_tokens(tokens);
                     tokens.append(";");
                     len.to_tokens(tokens);
                     tokens.append("]");
                 }
-                Ty::Ptr(ref target) => {
+                TyKind::Ptr(ref target) => {
                     tokens.append("*");
                     match target.mutability {
                         Mutability::Mutable => tokens.append("mut"),
@@ -605,19 +742,19 @@ mod printing {
                     }
                     target.ty.to_tokens(tokens);
                 }
-                Ty::Rptr(ref lifetime, ref target) => {
+                TyKind::Rptr(ref lifetime, ref target) => {
                     tokens.append("&");
                     lifetime.to_tokens(tokens);
                     target.mutability.to_tokens(tokens);
                     target.ty.to_tokens(tokens);
                 }
-                Ty::BareFn(ref func) => {
+                TyKind::BareFn(ref func) => {
                     func.to_tokens(tokens);
                 }
-                Ty::Never => {
+                TyKind::Never => {
                     tokens.append("!");
                 }
-                Ty::Tup(ref elems) => {
+                TyKind::Tup(ref elems) => {
                     tokens.append("(");
                     tokens.append_separated(elems, ",");
                     if elems.len() == 1 {
@@ -625,10 +762,10 @@ mod printing {
                     }
                     tokens.append(")");
                 }
-                Ty::Path(None, ref path) => {
+                TyKind::Path(None, ref path) => {
                     path.to_tokens(tokens);
                 }
-                Ty::Path(Some(ref qself), ref path) => {
+                TyKind::Path(Some(ref qself), ref path) => {
                     tokens.append("<");
                     qself.ty.to_tokens(tokens);
                     if qself.position > 0 {
@@ -649,22 +786,25 @@ mod printing {
                         segment.to_tokens(tokens);
                     }
                 }
-                Ty::TraitObject(ref bounds) => {
+                TyKind::TraitObject(ref bounds, syntax) => {
+                    if let TraitObjectSyntax::Dyn = syntax {
+                        tokens.append("dyn");
+                    }
                     tokens.append_separated(bounds, "+");
                 }
-                Ty::ImplTrait(ref bounds) => {
+                TyKind::ImplTrait(ref bounds) => {
                     tokens.append("impl");
                     tokens.append_separated(bounds, "+");
                 }
-                Ty::Paren(ref inner) => {
+                TyKind::Paren(ref inner) => {
                     tokens.append("(");
                     inner.to_tokens(tokens);
                     tokens.append(")");
                 }
-                Ty::Infer => {
+                TyKind::Infer => {
                     tokens.append("_");
                 }
-                Ty::Mac(ref mac) => mac.to_tokens(tokens),
+                TyKind::Mac(ref mac) => mac.to_tokens(tokens),
             }
         }
     }
@@ -716,9 +856,9 @@ mod printing {
     impl ToTokens for AngleBracketedParameterData {
         fn to_tokens(&self, tokens: &mut Tokens) {
             let has_lifetimes = !self.lifetimes.is_empty();
-            let has_types = !self.types.is_empty();
+            let has_args = !self.args.is_empty();
             let has_bindings = !self.bindings.is_empty();
-            if !has_lifetimes && !has_types && !has_bindings {
+            if !has_lifetimes && !has_args && !has_bindings {
                 return;
             }
 
@@ -732,11 +872,11 @@ mod printing {
                 lifetime.to_tokens(tokens);
                 first = false;
             }
-            for ty in &self.types {
+            for arg in &self.args {
                 if !first {
                     tokens.append(",");
                 }
-                ty.to_tokens(tokens);
+                arg.to_tokens(tokens);
                 first = false;
             }
             for binding in &self.bindings {
@@ -751,6 +891,15 @@ mod printing {
         }
     }
 
+    impl ToTokens for GenericArg {
+        fn to_tokens(&self, tokens: &mut Tokens) {
+            match *self {
+                GenericArg::Type(ref ty) => ty.to_tokens(tokens),
+                GenericArg::Const(ref expr) => expr.to_tokens(tokens),
+            }
+        }
+    }
+
     impl ToTokens for TypeBinding {
         fn to_tokens(&self, tokens: &mut Tokens) {
             self.ident.to_tokens(tokens);
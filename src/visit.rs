@@ -118,6 +118,10 @@ pub trait Visitor: Sized {
         walk_method_sig(self, method_sig);
     }
     #[cfg(feature = "full")]
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+    #[cfg(feature = "full")]
     fn visit_stmt(&mut self, stmt: &Stmt) {
         walk_stmt(self, stmt);
     }
@@ -131,6 +135,246 @@ pub trait Visitor: Sized {
     }
 }
 
+/// Like `Visitor`, but each node is borrowed for the lifetime `'ast` of the
+/// tree being walked rather than for the duration of the call, so a visitor
+/// can hold on to the nodes it visits.
+///
+/// Every `Visitor` is also a `Visit`, and both are driven by the same `walk_*`
+/// functions.
+pub trait Visit<'ast>: Sized {
+    fn visit_ident(&mut self, _ident: &'ast Ident) {}
+    fn visit_derive_input(&mut self, derive_input: &'ast DeriveInput) {
+        walk_derive_input(self, derive_input)
+    }
+    fn visit_ty(&mut self, ty: &'ast Ty) {
+        walk_ty(self, ty)
+    }
+    fn visit_generics(&mut self, generics: &'ast Generics) {
+        walk_generics(self, generics)
+    }
+    fn visit_ty_param_bound(&mut self, bound: &'ast TyParamBound) {
+        walk_ty_param_bound(self, bound)
+    }
+    fn visit_poly_trait_ref(&mut self,
+                            trait_ref: &'ast PolyTraitRef,
+                            modifier: &'ast TraitBoundModifier) {
+        walk_poly_trait_ref(self, trait_ref, modifier)
+    }
+    fn visit_variant_data(&mut self,
+                          data: &'ast VariantData,
+                          _ident: &'ast Ident,
+                          _generics: &'ast Generics) {
+        walk_variant_data(self, data)
+    }
+    fn visit_field(&mut self, field: &'ast Field) {
+        walk_field(self, field)
+    }
+    fn visit_variant(&mut self, variant: &'ast Variant, generics: &'ast Generics) {
+        walk_variant(self, variant, generics)
+    }
+    fn visit_lifetime(&mut self, _lifetime: &'ast Lifetime) {}
+    fn visit_lifetime_def(&mut self, lifetime: &'ast LifetimeDef) {
+        walk_lifetime_def(self, lifetime)
+    }
+    fn visit_path(&mut self, path: &'ast Path) {
+        walk_path(self, path)
+    }
+    fn visit_path_segment(&mut self, path_segment: &'ast PathSegment) {
+        walk_path_segment(self, path_segment)
+    }
+    fn visit_path_parameters(&mut self, path_parameters: &'ast PathParameters) {
+        walk_path_parameters(self, path_parameters)
+    }
+    fn visit_assoc_type_binding(&mut self, type_binding: &'ast TypeBinding) {
+        walk_assoc_type_binding(self, type_binding)
+    }
+    fn visit_attribute(&mut self, _attr: &'ast Attribute) {}
+    fn visit_fn_ret_ty(&mut self, ret_ty: &'ast FunctionRetTy) {
+        walk_fn_ret_ty(self, ret_ty)
+    }
+    fn visit_const_expr(&mut self, expr: &'ast ConstExpr) {
+        walk_const_expr(self, expr)
+    }
+    fn visit_lit(&mut self, _lit: &'ast Lit) {}
+
+    fn visit_mac(&mut self, mac: &'ast Mac) {
+        walk_mac(self, mac);
+    }
+
+    #[cfg(feature = "full")]
+    fn visit_crate(&mut self, _crate: &'ast Crate) {
+        walk_crate(self, _crate);
+    }
+    #[cfg(feature = "full")]
+    fn visit_item(&mut self, item: &'ast Item) {
+        walk_item(self, item);
+    }
+    #[cfg(feature = "full")]
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        walk_expr(self, expr);
+    }
+    #[cfg(feature = "full")]
+    fn visit_foreign_item(&mut self, foreign_item: &'ast ForeignItem) {
+        walk_foreign_item(self, foreign_item);
+    }
+    #[cfg(feature = "full")]
+    fn visit_pat(&mut self, pat: &'ast Pat) {
+        walk_pat(self, pat);
+    }
+    #[cfg(feature = "full")]
+    fn visit_fn_decl(&mut self, fn_decl: &'ast FnDecl) {
+        walk_fn_decl(self, fn_decl);
+    }
+    #[cfg(feature = "full")]
+    fn visit_trait_item(&mut self, trait_item: &'ast TraitItem) {
+        walk_trait_item(self, trait_item);
+    }
+    #[cfg(feature = "full")]
+    fn visit_impl_item(&mut self, impl_item: &'ast ImplItem) {
+        walk_impl_item(self, impl_item);
+    }
+    #[cfg(feature = "full")]
+    fn visit_method_sig(&mut self, method_sig: &'ast MethodSig) {
+        walk_method_sig(self, method_sig);
+    }
+    #[cfg(feature = "full")]
+    fn visit_block(&mut self, block: &'ast Block) {
+        walk_block(self, block);
+    }
+    #[cfg(feature = "full")]
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        walk_stmt(self, stmt);
+    }
+    #[cfg(feature = "full")]
+    fn visit_local(&mut self, local: &'ast Local) {
+        walk_local(self, local);
+    }
+    #[cfg(feature = "full")]
+    fn visit_view_path(&mut self, view_path: &'ast ViewPath) {
+        walk_view_path(self, view_path);
+    }
+}
+
+impl<'ast, V: Visitor> Visit<'ast> for V {
+    fn visit_ident(&mut self, ident: &'ast Ident) {
+        Visitor::visit_ident(self, ident)
+    }
+    fn visit_derive_input(&mut self, derive_input: &'ast DeriveInput) {
+        Visitor::visit_derive_input(self, derive_input)
+    }
+    fn visit_ty(&mut self, ty: &'ast Ty) {
+        Visitor::visit_ty(self, ty)
+    }
+    fn visit_generics(&mut self, generics: &'ast Generics) {
+        Visitor::visit_generics(self, generics)
+    }
+    fn visit_ty_param_bound(&mut self, bound: &'ast TyParamBound) {
+        Visitor::visit_ty_param_bound(self, bound)
+    }
+    fn visit_poly_trait_ref(&mut self,
+                            trait_ref: &'ast PolyTraitRef,
+                            modifier: &'ast TraitBoundModifier) {
+        Visitor::visit_poly_trait_ref(self, trait_ref, modifier)
+    }
+    fn visit_variant_data(&mut self,
+                          data: &'ast VariantData,
+                          ident: &'ast Ident,
+                          generics: &'ast Generics) {
+        Visitor::visit_variant_data(self, data, ident, generics)
+    }
+    fn visit_field(&mut self, field: &'ast Field) {
+        Visitor::visit_field(self, field)
+    }
+    fn visit_variant(&mut self, variant: &'ast Variant, generics: &'ast Generics) {
+        Visitor::visit_variant(self, variant, generics)
+    }
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        Visitor::visit_lifetime(self, lifetime)
+    }
+    fn visit_lifetime_def(&mut self, lifetime: &'ast LifetimeDef) {
+        Visitor::visit_lifetime_def(self, lifetime)
+    }
+    fn visit_path(&mut self, path: &'ast Path) {
+        Visitor::visit_path(self, path)
+    }
+    fn visit_path_segment(&mut self, path_segment: &'ast PathSegment) {
+        Visitor::visit_path_segment(self, path_segment)
+    }
+    fn visit_path_parameters(&mut self, path_parameters: &'ast PathParameters) {
+        Visitor::visit_path_parameters(self, path_parameters)
+    }
+    fn visit_assoc_type_binding(&mut self, type_binding: &'ast TypeBinding) {
+        Visitor::visit_assoc_type_binding(self, type_binding)
+    }
+    fn visit_attribute(&mut self, attr: &'ast Attribute) {
+        Visitor::visit_attribute(self, attr)
+    }
+    fn visit_fn_ret_ty(&mut self, ret_ty: &'ast FunctionRetTy) {
+        Visitor::visit_fn_ret_ty(self, ret_ty)
+    }
+    fn visit_const_expr(&mut self, expr: &'ast ConstExpr) {
+        Visitor::visit_const_expr(self, expr)
+    }
+    fn visit_lit(&mut self, lit: &'ast Lit) {
+        Visitor::visit_lit(self, lit)
+    }
+    fn visit_mac(&mut self, mac: &'ast Mac) {
+        Visitor::visit_mac(self, mac)
+    }
+    #[cfg(feature = "full")]
+    fn visit_crate(&mut self, krate: &'ast Crate) {
+        Visitor::visit_crate(self, krate)
+    }
+    #[cfg(feature = "full")]
+    fn visit_item(&mut self, item: &'ast Item) {
+        Visitor::visit_item(self, item)
+    }
+    #[cfg(feature = "full")]
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        Visitor::visit_expr(self, expr)
+    }
+    #[cfg(feature = "full")]
+    fn visit_foreign_item(&mut self, foreign_item: &'ast ForeignItem) {
+        Visitor::visit_foreign_item(self, foreign_item)
+    }
+    #[cfg(feature = "full")]
+    fn visit_pat(&mut self, pat: &'ast Pat) {
+        Visitor::visit_pat(self, pat)
+    }
+    #[cfg(feature = "full")]
+    fn visit_fn_decl(&mut self, fn_decl: &'ast FnDecl) {
+        Visitor::visit_fn_decl(self, fn_decl)
+    }
+    #[cfg(feature = "full")]
+    fn visit_trait_item(&mut self, trait_item: &'ast TraitItem) {
+        Visitor::visit_trait_item(self, trait_item)
+    }
+    #[cfg(feature = "full")]
+    fn visit_impl_item(&mut self, impl_item: &'ast ImplItem) {
+        Visitor::visit_impl_item(self, impl_item)
+    }
+    #[cfg(feature = "full")]
+    fn visit_method_sig(&mut self, method_sig: &'ast MethodSig) {
+        Visitor::visit_method_sig(self, method_sig)
+    }
+    #[cfg(feature = "full")]
+    fn visit_block(&mut self, block: &'ast Block) {
+        Visitor::visit_block(self, block)
+    }
+    #[cfg(feature = "full")]
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        Visitor::visit_stmt(self, stmt)
+    }
+    #[cfg(feature = "full")]
+    fn visit_local(&mut self, local: &'ast Local) {
+        Visitor::visit_local(self, local)
+    }
+    #[cfg(feature = "full")]
+    fn visit_view_path(&mut self, view_path: &'ast ViewPath) {
+        Visitor::visit_view_path(self, view_path)
+    }
+}
+
 macro_rules! walk_list {
     ($visitor:expr, $method:ident, $list:expr $(, $extra_args:expr)*) => {
         for elem in $list {
@@ -139,25 +383,27 @@ macro_rules! walk_list {
     };
 }
 
-pub fn walk_opt_ident<V: Visitor>(visitor: &mut V, opt_ident: &Option<Ident>) {
+pub fn walk_opt_ident<'ast, V: Visit<'ast>>(visitor: &mut V, opt_ident: &'ast Option<Ident>) {
     if let Some(ref ident) = *opt_ident {
         visitor.visit_ident(ident);
     }
 }
 
-pub fn walk_lifetime_def<V: Visitor>(visitor: &mut V, lifetime_def: &LifetimeDef) {
+pub fn walk_lifetime_def<'ast, V: Visit<'ast>>(visitor: &mut V, lifetime_def: &'ast LifetimeDef) {
     visitor.visit_lifetime(&lifetime_def.lifetime);
     walk_list!(visitor, visit_lifetime, &lifetime_def.bounds);
 }
 
-pub fn walk_poly_trait_ref<V>(visitor: &mut V, trait_ref: &PolyTraitRef, _: &TraitBoundModifier)
-    where V: Visitor
+pub fn walk_poly_trait_ref<'ast, V>(visitor: &mut V,
+                                    trait_ref: &'ast PolyTraitRef,
+                                    _: &'ast TraitBoundModifier)
+    where V: Visit<'ast>
 {
     walk_list!(visitor, visit_lifetime_def, &trait_ref.bound_lifetimes);
     visitor.visit_path(&trait_ref.trait_ref);
 }
 
-pub fn walk_derive_input<V: Visitor>(visitor: &mut V, derive_input: &DeriveInput) {
+pub fn walk_derive_input<'ast, V: Visit<'ast>>(visitor: &mut V, derive_input: &'ast DeriveInput) {
     visitor.visit_ident(&derive_input.ident);
     visitor.visit_generics(&derive_input.generics);
     match derive_input.body {
@@ -171,28 +417,28 @@ pub fn walk_derive_input<V: Visitor>(visitor: &mut V, derive_input: &DeriveInput
     walk_list!(visitor, visit_attribute, &derive_input.attrs);
 }
 
-pub fn walk_variant<V>(visitor: &mut V, variant: &Variant, generics: &Generics)
-    where V: Visitor
+pub fn walk_variant<'ast, V>(visitor: &mut V, variant: &'ast Variant, generics: &'ast Generics)
+    where V: Visit<'ast>
 {
     visitor.visit_ident(&variant.ident);
     visitor.visit_variant_data(&variant.data, &variant.ident, generics);
     walk_list!(visitor, visit_attribute, &variant.attrs);
 }
 
-pub fn walk_ty<V: Visitor>(visitor: &mut V, ty: &Ty) {
-    match *ty {
-        Ty::Slice(ref inner) |
-        Ty::Paren(ref inner) => visitor.visit_ty(inner),
-        Ty::Ptr(ref mutable_type) => visitor.visit_ty(&mutable_type.ty),
-        Ty::Rptr(ref opt_lifetime, ref mutable_type) => {
+pub fn walk_ty<'ast, V: Visit<'ast>>(visitor: &mut V, ty: &'ast Ty) {
+    match ty.node {
+        TyKind::Slice(ref inner) |
+        TyKind::Paren(ref inner) => visitor.visit_ty(inner),
+        TyKind::Ptr(ref mutable_type) => visitor.visit_ty(&mutable_type.ty),
+        TyKind::Rptr(ref opt_lifetime, ref mutable_type) => {
             walk_list!(visitor, visit_lifetime, opt_lifetime);
             visitor.visit_ty(&mutable_type.ty)
         }
-        Ty::Never | Ty::Infer => {}
-        Ty::Tup(ref tuple_element_types) => {
+        TyKind::Never | TyKind::Infer => {}
+        TyKind::Tup(ref tuple_element_types) => {
             walk_list!(visitor, visit_ty, tuple_element_types);
         }
-        Ty::BareFn(ref bare_fn) => {
+        TyKind::BareFn(ref bare_fn) => {
             walk_list!(visitor, visit_lifetime_def, &bare_fn.lifetimes);
             for argument in &bare_fn.inputs {
                 walk_opt_ident(visitor, &argument.name);
@@ -200,43 +446,48 @@ pub fn walk_ty<V: Visitor>(visitor: &mut V, ty: &Ty) {
             }
             visitor.visit_fn_ret_ty(&bare_fn.output)
         }
-        Ty::Path(ref maybe_qself, ref path) => {
+        TyKind::Path(ref maybe_qself, ref path) => {
             if let Some(ref qself) = *maybe_qself {
                 visitor.visit_ty(&qself.ty);
             }
             visitor.visit_path(path);
         }
-        Ty::Array(ref inner, ref len) => {
+        TyKind::Array(ref inner, ref len) => {
             visitor.visit_ty(inner);
             visitor.visit_const_expr(len);
         }
-        Ty::TraitObject(ref bounds) |
-        Ty::ImplTrait(ref bounds) => {
+        TyKind::TraitObject(ref bounds, _) |
+        TyKind::ImplTrait(ref bounds) => {
             walk_list!(visitor, visit_ty_param_bound, bounds);
         }
-        Ty::Mac(ref mac) => {
+        TyKind::Mac(ref mac) => {
             visitor.visit_mac(mac);
         }
     }
 }
 
-pub fn walk_path<V: Visitor>(visitor: &mut V, path: &Path) {
+pub fn walk_path<'ast, V: Visit<'ast>>(visitor: &mut V, path: &'ast Path) {
     for segment in &path.segments {
         visitor.visit_path_segment(segment);
     }
 }
 
-pub fn walk_path_segment<V: Visitor>(visitor: &mut V, segment: &PathSegment) {
+pub fn walk_path_segment<'ast, V: Visit<'ast>>(visitor: &mut V, segment: &'ast PathSegment) {
     visitor.visit_ident(&segment.ident);
     visitor.visit_path_parameters(&segment.parameters);
 }
 
-pub fn walk_path_parameters<V>(visitor: &mut V, path_parameters: &PathParameters)
-    where V: Visitor
+pub fn walk_path_parameters<'ast, V>(visitor: &mut V, path_parameters: &'ast PathParameters)
+    where V: Visit<'ast>
 {
     match *path_parameters {
         PathParameters::AngleBracketed(ref data) => {
-            walk_list!(visitor, visit_ty, &data.types);
+            for arg in &data.args {
+                match *arg {
+                    GenericArg::Type(ref ty) => visitor.visit_ty(ty),
+                    GenericArg::Const(ref expr) => visitor.visit_const_expr(expr),
+                }
+            }
             walk_list!(visitor, visit_lifetime, &data.lifetimes);
             walk_list!(visitor, visit_assoc_type_binding, &data.bindings);
         }
@@ -247,12 +498,14 @@ pub fn walk_path_parameters<V>(visitor: &mut V, path_parameters: &PathParameters
     }
 }
 
-pub fn walk_assoc_type_binding<V: Visitor>(visitor: &mut V, type_binding: &TypeBinding) {
+pub fn walk_assoc_type_binding<'ast, V>(visitor: &mut V, type_binding: &'ast TypeBinding)
+    where V: Visit<'ast>
+{
     visitor.visit_ident(&type_binding.ident);
     visitor.visit_ty(&type_binding.ty);
 }
 
-pub fn walk_ty_param_bound<V: Visitor>(visitor: &mut V, bound: &TyParamBound) {
+pub fn walk_ty_param_bound<'ast, V: Visit<'ast>>(visitor: &mut V, bound: &'ast TyParamBound) {
     match *bound {
         TyParamBound::Trait(ref ty, ref modifier) => {
             visitor.visit_poly_trait_ref(ty, modifier);
@@ -263,11 +516,22 @@ pub fn walk_ty_param_bound<V: Visitor>(visitor: &mut V, bound: &TyParamBound) {
     }
 }
 
-pub fn walk_generics<V: Visitor>(visitor: &mut V, generics: &Generics) {
-    for param in &generics.ty_params {
-        visitor.visit_ident(&param.ident);
-        walk_list!(visitor, visit_ty_param_bound, &param.bounds);
-        walk_list!(visitor, visit_ty, &param.default);
+pub fn walk_generics<'ast, V: Visit<'ast>>(visitor: &mut V, generics: &'ast Generics) {
+    for param in &generics.params {
+        match *param {
+            GenericParam::Type(ref param) => {
+                visitor.visit_ident(&param.ident);
+                walk_list!(visitor, visit_ty_param_bound, &param.bounds);
+                walk_list!(visitor, visit_ty, &param.default);
+            }
+            GenericParam::Const(ref param) => {
+                visitor.visit_ident(&param.ident);
+                visitor.visit_ty(&param.ty);
+                if let Some(ref default) = param.default {
+                    visitor.visit_const_expr(default);
+                }
+            }
+        }
     }
     walk_list!(visitor, visit_lifetime_def, &generics.lifetimes);
     for predicate in &generics.where_clause.predicates {
@@ -296,23 +560,23 @@ pub fn walk_generics<V: Visitor>(visitor: &mut V, generics: &Generics) {
     }
 }
 
-pub fn walk_fn_ret_ty<V: Visitor>(visitor: &mut V, ret_ty: &FunctionRetTy) {
+pub fn walk_fn_ret_ty<'ast, V: Visit<'ast>>(visitor: &mut V, ret_ty: &'ast FunctionRetTy) {
     if let FunctionRetTy::Ty(ref output_ty) = *ret_ty {
         visitor.visit_ty(output_ty)
     }
 }
 
-pub fn walk_variant_data<V: Visitor>(visitor: &mut V, data: &VariantData) {
+pub fn walk_variant_data<'ast, V: Visit<'ast>>(visitor: &mut V, data: &'ast VariantData) {
     walk_list!(visitor, visit_field, data.fields());
 }
 
-pub fn walk_field<V: Visitor>(visitor: &mut V, field: &Field) {
+pub fn walk_field<'ast, V: Visit<'ast>>(visitor: &mut V, field: &'ast Field) {
     walk_opt_ident(visitor, &field.ident);
     visitor.visit_ty(&field.ty);
     walk_list!(visitor, visit_attribute, &field.attrs);
 }
 
-pub fn walk_const_expr<V: Visitor>(visitor: &mut V, len: &ConstExpr) {
+pub fn walk_const_expr<'ast, V: Visit<'ast>>(visitor: &mut V, len: &'ast ConstExpr) {
     match *len {
         ConstExpr::Call(ref function, ref args) => {
             visitor.visit_const_expr(function);
@@ -339,33 +603,34 @@ pub fn walk_const_expr<V: Visitor>(visitor: &mut V, len: &ConstExpr) {
             visitor.visit_const_expr(expr);
             visitor.visit_const_expr(index);
         }
-        ConstExpr::Paren(ref expr) => {
+        ConstExpr::Paren(ref expr) |
+        ConstExpr::Block(ref expr) => {
             visitor.visit_const_expr(expr);
         }
         ConstExpr::Other(ref other) => {
             #[cfg(feature = "full")]
-            fn walk_other<V: Visitor>(visitor: &mut V, other: &Expr) {
+            fn walk_other<'ast, V: Visit<'ast>>(visitor: &mut V, other: &'ast Expr) {
                 visitor.visit_expr(other);
             }
             #[cfg(not(feature = "full"))]
-            fn walk_other<V: Visitor>(_: &mut V, _: &super::constant::Other) {}
+            fn walk_other<'ast, V: Visit<'ast>>(_: &mut V, _: &'ast super::constant::Other) {}
             walk_other(visitor, other);
         }
     }
 }
 
-pub fn walk_mac<V: Visitor>(visitor: &mut V, mac: &Mac) {
+pub fn walk_mac<'ast, V: Visit<'ast>>(visitor: &mut V, mac: &'ast Mac) {
     visitor.visit_path(&mac.path);
 }
 
 #[cfg(feature = "full")]
-pub fn walk_crate<V: Visitor>(visitor: &mut V, _crate: &Crate) {
+pub fn walk_crate<'ast, V: Visit<'ast>>(visitor: &mut V, _crate: &'ast Crate) {
     walk_list!(visitor, visit_attribute, &_crate.attrs);
     walk_list!(visitor, visit_item, &_crate.items);
 }
 
 #[cfg(feature = "full")]
-pub fn walk_item<V: Visitor>(visitor: &mut V, item: &Item) {
+pub fn walk_item<'ast, V: Visit<'ast>>(visitor: &mut V, item: &'ast Item) {
     visitor.visit_ident(&item.ident);
     walk_list!(visitor, visit_attribute, &item.attrs);
     match item.node {
@@ -383,10 +648,10 @@ pub fn walk_item<V: Visitor>(visitor: &mut V, item: &Item) {
             visitor.visit_ty(ty);
             visitor.visit_expr(expr);
         }
-        ItemKind::Fn(ref decl, _, _, _, ref generics, ref body) => {
+        ItemKind::Fn(ref decl, _, _, _, _, ref generics, ref body) => {
             visitor.visit_fn_decl(decl);
             visitor.visit_generics(generics);
-            walk_list!(visitor, visit_stmt, &body.stmts);
+            visitor.visit_block(body);
         }
         ItemKind::Mod(ref maybe_items) => {
             if let Some(ref items) = *maybe_items {
@@ -428,11 +693,12 @@ pub fn walk_item<V: Visitor>(visitor: &mut V, item: &Item) {
         ItemKind::Mac(ref mac) => {
             visitor.visit_mac(mac)
         }
+        ItemKind::Error(_) => {}
     }
 }
 
 #[cfg(feature = "full")]
-pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
+pub fn walk_expr<'ast, V: Visit<'ast>>(visitor: &mut V, expr: &'ast Expr) {
     walk_list!(visitor, visit_attribute, &expr.attrs);
     match expr.node {
         ExprKind::Box(ref expr) => {
@@ -477,7 +743,7 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
         }
         ExprKind::If(ref cond, ref cons, ref maybe_alt) => {
             visitor.visit_expr(cond);
-            walk_list!(visitor, visit_stmt, &cons.stmts);
+            visitor.visit_block(cons);
             if let Some(ref alt) = *maybe_alt {
                 visitor.visit_expr(alt);
             }
@@ -485,35 +751,35 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
         ExprKind::IfLet(ref pat, ref cond, ref cons, ref maybe_alt) => {
             visitor.visit_pat(pat);
             visitor.visit_expr(cond);
-            walk_list!(visitor, visit_stmt, &cons.stmts);
+            visitor.visit_block(cons);
             if let Some(ref alt) = *maybe_alt {
                 visitor.visit_expr(alt);
             }
         }
         ExprKind::While(ref cond, ref body, ref label) => {
             visitor.visit_expr(cond);
-            walk_list!(visitor, visit_stmt, &body.stmts);
+            visitor.visit_block(body);
             walk_opt_ident(visitor, label);
         }
         ExprKind::WhileLet(ref pat, ref cond, ref body, ref label) => {
             visitor.visit_pat(pat);
             visitor.visit_expr(cond);
-            walk_list!(visitor, visit_stmt, &body.stmts);
+            visitor.visit_block(body);
             walk_opt_ident(visitor, label);
         }
         ExprKind::ForLoop(ref pat, ref expr, ref body, ref label) => {
             visitor.visit_pat(pat);
             visitor.visit_expr(expr);
-            walk_list!(visitor, visit_stmt, &body.stmts);
+            visitor.visit_block(body);
             walk_opt_ident(visitor, label);
         }
         ExprKind::Loop(ref body, ref label) => {
-            walk_list!(visitor, visit_stmt, &body.stmts);
+            visitor.visit_block(body);
             walk_opt_ident(visitor, label);
         }
         ExprKind::Match(ref expr, ref arms) => {
             visitor.visit_expr(expr);
-            for &Arm{ref attrs, ref pats, ref guard, ref body} in arms {
+            for &Arm{ref attrs, ref pats, ref guard, ref body, ..} in arms {
                 walk_list!(visitor, visit_attribute, attrs);
                 walk_list!(visitor, visit_pat, pats);
                 if let Some(ref guard) = *guard {
@@ -522,12 +788,16 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
                 visitor.visit_expr(body);
             }
         }
-        ExprKind::Closure(_, ref decl, ref expr) => {
+        ExprKind::Closure(_, _, ref decl, ref expr) => {
             visitor.visit_fn_decl(decl);
             visitor.visit_expr(expr);
         }
-        ExprKind::Block(_, ref block) => {
-            walk_list!(visitor, visit_stmt, &block.stmts);
+        ExprKind::Block(_, ref block) |
+        ExprKind::Async(_, ref block) => {
+            visitor.visit_block(block);
+        }
+        ExprKind::Await(ref expr) => {
+            visitor.visit_expr(expr);
         }
         ExprKind::Assign(ref lhs, ref rhs) => {
             visitor.visit_expr(lhs);
@@ -606,7 +876,7 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
 }
 
 #[cfg(feature = "full")]
-pub fn walk_foreign_item<V: Visitor>(visitor: &mut V, foreign_item: &ForeignItem) {
+pub fn walk_foreign_item<'ast, V: Visit<'ast>>(visitor: &mut V, foreign_item: &'ast ForeignItem) {
     visitor.visit_ident(&foreign_item.ident);
     walk_list!(visitor, visit_attribute, &foreign_item.attrs);
     match foreign_item.node {
@@ -621,61 +891,61 @@ pub fn walk_foreign_item<V: Visitor>(visitor: &mut V, foreign_item: &ForeignItem
 }
 
 #[cfg(feature = "full")]
-pub fn walk_pat<V: Visitor>(visitor: &mut V, pat: &Pat) {
-    match *pat {
-        Pat::Wild => {}
-        Pat::Ident(_, ref ident, ref maybe_pat) => {
+pub fn walk_pat<'ast, V: Visit<'ast>>(visitor: &mut V, pat: &'ast Pat) {
+    match pat.node {
+        PatKind::Wild => {}
+        PatKind::Ident(_, ref ident, ref maybe_pat) => {
             visitor.visit_ident(ident);
             if let Some(ref pat) = *maybe_pat {
                 visitor.visit_pat(pat);
             }
         }
-        Pat::Struct(ref path, ref field_pats, _) => {
+        PatKind::Struct(ref path, ref field_pats, _) => {
             visitor.visit_path(path);
             for &FieldPat{ref ident, ref pat, ..} in field_pats {
                 visitor.visit_ident(ident);
                 visitor.visit_pat(pat);
             }
         }
-        Pat::TupleStruct(ref path, ref pats, _) => {
+        PatKind::TupleStruct(ref path, ref pats, _) => {
             visitor.visit_path(path);
             walk_list!(visitor, visit_pat, pats);
         }
-        Pat::Path(ref maybe_qself, ref path) => {
+        PatKind::Path(ref maybe_qself, ref path) => {
             if let Some(ref qself) = *maybe_qself {
                 visitor.visit_ty(&qself.ty);
             }
             visitor.visit_path(path);
         }
-        Pat::Tuple(ref pats, _) => {
+        PatKind::Tuple(ref pats, _) => {
             walk_list!(visitor, visit_pat, pats);
         }
-        Pat::Box(ref pat) |
-        Pat::Ref(ref pat, _) => {
+        PatKind::Box(ref pat) |
+        PatKind::Ref(ref pat, _) => {
             visitor.visit_pat(pat);
         }
-        Pat::Lit(ref expr) => {
+        PatKind::Lit(ref expr) => {
             visitor.visit_expr(expr);
         }
-        Pat::Range(ref start, ref end) => {
+        PatKind::Range(ref start, ref end) => {
             visitor.visit_expr(start);
             visitor.visit_expr(end);
         }
-        Pat::Slice(ref start, ref maybe_mid, ref end) => {
+        PatKind::Slice(ref start, ref maybe_mid, ref end) => {
             walk_list!(visitor, visit_pat, start);
             if let Some(ref mid) = *maybe_mid {
                 visitor.visit_pat(mid);
             }
             walk_list!(visitor, visit_pat, end);
         }
-        Pat::Mac(ref mac) => {
+        PatKind::Mac(ref mac) => {
             visitor.visit_mac(mac);
         }
     }
 }
 
 #[cfg(feature = "full")]
-pub fn walk_fn_decl<V: Visitor>(visitor: &mut V, fn_decl: &FnDecl) {
+pub fn walk_fn_decl<'ast, V: Visit<'ast>>(visitor: &mut V, fn_decl: &'ast FnDecl) {
     for input in &fn_decl.inputs {
         match *input {
             FnArg::SelfRef(_, _) | FnArg::SelfValue(_) => {}
@@ -692,7 +962,7 @@ pub fn walk_fn_decl<V: Visitor>(visitor: &mut V, fn_decl: &FnDecl) {
 }
 
 #[cfg(feature = "full")]
-pub fn walk_trait_item<V: Visitor>(visitor: &mut V, trait_item: &TraitItem) {
+pub fn walk_trait_item<'ast, V: Visit<'ast>>(visitor: &mut V, trait_item: &'ast TraitItem) {
     visitor.visit_ident(&trait_item.ident);
     walk_list!(visitor, visit_attribute, &trait_item.attrs);
     match trait_item.node {
@@ -705,10 +975,11 @@ pub fn walk_trait_item<V: Visitor>(visitor: &mut V, trait_item: &TraitItem) {
         TraitItemKind::Method(ref method_sig, ref maybe_block) => {
             visitor.visit_method_sig(method_sig);
             if let Some(ref block) = *maybe_block {
-                walk_list!(visitor, visit_stmt, &block.stmts);
+                visitor.visit_block(block);
             }
         }
-        TraitItemKind::Type(ref bounds, ref maybe_ty) => {
+        TraitItemKind::Type(ref generics, ref bounds, ref maybe_ty) => {
+            visitor.visit_generics(generics);
             walk_list!(visitor, visit_ty_param_bound, bounds);
             if let Some(ref ty) = *maybe_ty {
                 visitor.visit_ty(ty);
@@ -721,7 +992,7 @@ pub fn walk_trait_item<V: Visitor>(visitor: &mut V, trait_item: &TraitItem) {
 }
 
 #[cfg(feature = "full")]
-pub fn walk_impl_item<V: Visitor>(visitor: &mut V, impl_item: &ImplItem) {
+pub fn walk_impl_item<'ast, V: Visit<'ast>>(visitor: &mut V, impl_item: &'ast ImplItem) {
     visitor.visit_ident(&impl_item.ident);
     walk_list!(visitor, visit_attribute, &impl_item.attrs);
     match impl_item.node {
@@ -731,9 +1002,10 @@ pub fn walk_impl_item<V: Visitor>(visitor: &mut V, impl_item: &ImplItem) {
         }
         ImplItemKind::Method(ref method_sig, ref block) => {
             visitor.visit_method_sig(method_sig);
-            walk_list!(visitor, visit_stmt, &block.stmts);
+            visitor.visit_block(block);
         }
-        ImplItemKind::Type(ref ty) => {
+        ImplItemKind::Type(ref generics, ref ty) => {
+            visitor.visit_generics(generics);
             visitor.visit_ty(ty);
         }
         ImplItemKind::Macro(ref mac) => {
@@ -743,25 +1015,30 @@ pub fn walk_impl_item<V: Visitor>(visitor: &mut V, impl_item: &ImplItem) {
 }
 
 #[cfg(feature = "full")]
-pub fn walk_method_sig<V: Visitor>(visitor: &mut V, method_sig: &MethodSig) {
+pub fn walk_method_sig<'ast, V: Visit<'ast>>(visitor: &mut V, method_sig: &'ast MethodSig) {
     visitor.visit_fn_decl(&method_sig.decl);
     visitor.visit_generics(&method_sig.generics);
 }
 
 #[cfg(feature = "full")]
-pub fn walk_stmt<V: Visitor>(visitor: &mut V, stmt: &Stmt) {
-    match *stmt {
-        Stmt::Local(ref local) => {
+pub fn walk_block<'ast, V: Visit<'ast>>(visitor: &mut V, block: &'ast Block) {
+    walk_list!(visitor, visit_stmt, &block.stmts);
+}
+
+#[cfg(feature = "full")]
+pub fn walk_stmt<'ast, V: Visit<'ast>>(visitor: &mut V, stmt: &'ast Stmt) {
+    match stmt.node {
+        StmtKind::Local(ref local) => {
             visitor.visit_local(local);
         }
-        Stmt::Item(ref item) => {
+        StmtKind::Item(ref item) => {
             visitor.visit_item(item);
         }
-        Stmt::Expr(ref expr) |
-        Stmt::Semi(ref expr) => {
+        StmtKind::Expr(ref expr) |
+        StmtKind::Semi(ref expr) => {
             visitor.visit_expr(expr);
         }
-        Stmt::Mac(ref details) => {
+        StmtKind::Mac(ref details) => {
             let (ref mac, _, ref attrs) = **details;
             visitor.visit_mac(mac);
             walk_list!(visitor, visit_attribute, attrs);
@@ -770,7 +1047,7 @@ pub fn walk_stmt<V: Visitor>(visitor: &mut V, stmt: &Stmt) {
 }
 
 #[cfg(feature = "full")]
-pub fn walk_local<V: Visitor>(visitor: &mut V, local: &Local) {
+pub fn walk_local<'ast, V: Visit<'ast>>(visitor: &mut V, local: &'ast Local) {
     visitor.visit_pat(&local.pat);
     if let Some(ref ty) = local.ty {
         visitor.visit_ty(ty);
@@ -782,7 +1059,7 @@ pub fn walk_local<V: Visitor>(visitor: &mut V, local: &Local) {
 }
 
 #[cfg(feature = "full")]
-pub fn walk_view_path<V: Visitor>(visitor: &mut V, view_path: &ViewPath) {
+pub fn walk_view_path<'ast, V: Visit<'ast>>(visitor: &mut V, view_path: &'ast ViewPath) {
     match *view_path {
         ViewPath::Simple(ref path, ref maybe_ident) => {
             visitor.visit_path(path);
@@ -793,10 +1070,7 @@ pub fn walk_view_path<V: Visitor>(visitor: &mut V, view_path: &ViewPath) {
         }
         ViewPath::List(ref path, ref items) => {
             visitor.visit_path(path);
-            for &PathListItem{ref name, ref rename} in items {
-                visitor.visit_ident(name);
-                walk_opt_ident(visitor, rename);
-            }
+            walk_list!(visitor, visit_view_path, items);
         }
     }
 }
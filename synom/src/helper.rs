@@ -1,4 +1,4 @@
-use {IResult, ParseState};
+use {Expected, IResult, ParseState};
 use space::{skip_whitespace, word_break};
 
 /// Parse a piece of punctuation like "+" or "+=".
@@ -38,6 +38,7 @@ pub fn punct<'a>(input: ParseState<'a>, token: &'static str) -> IResult<ParseSta
     if input.starts_with(token) {
         IResult::Done(input.advance(token.len()), token)
     } else {
+        input.expected(Expected::Token(token));
         IResult::Error
     }
 }
@@ -84,15 +85,69 @@ macro_rules! keyword {
 // Not public API.
 #[doc(hidden)]
 pub fn keyword<'a>(input: ParseState<'a>, token: &'static str) -> IResult<ParseState<'a>, &'a str> {
-    match punct(input, token) {
-        IResult::Done(rest, _) => {
-            match word_break(rest) {
-                IResult::Done(_, _) => IResult::Done(rest, token),
-                IResult::Error => IResult::Error,
-            }
+    let input = skip_whitespace(input);
+    if input.starts_with(token) {
+        let rest = input.advance(token.len());
+        if let IResult::Done(_, _) = word_break(rest) {
+            return IResult::Done(rest, token);
         }
-        IResult::Error => IResult::Error,
     }
+    input.expected(Expected::Token(token));
+    IResult::Error
+}
+
+/// Report a failure of the given parser as `Expected::Named(NAME)` rather
+/// than as each of the tokens it tried, if it fails without getting past its
+/// first token.
+///
+/// - **Syntax:** `label!("NAME", THING)`
+/// - **Output:** `THING`
+///
+/// ```rust
+/// extern crate syn;
+/// #[macro_use] extern crate synom;
+///
+/// use synom::{Expected, FurthestFailure, IResult, ParseState};
+///
+/// named!(sign -> &str, label!("sign", alt!(punct!("+") | punct!("-"))));
+///
+/// fn main() {
+///     let failure = FurthestFailure::new();
+///     if let IResult::Done(..) = sign(ParseState::tracking("*", &failure)) {
+///         panic!("parsed a sign");
+///     }
+///     assert_eq!(failure.expected(), vec![Expected::Named("sign")]);
+/// }
+/// ```
+#[macro_export]
+macro_rules! label {
+    ($i:expr, $name:expr, $submac:ident!( $($args:tt)* )) => {
+        $crate::helper::label($i, $name, |i| $submac!(i, $($args)*))
+    };
+
+    ($i:expr, $name:expr, $f:expr) => {
+        label!($i, $name, call!($f))
+    };
+}
+
+// Not public API.
+#[doc(hidden)]
+pub fn label<'a, O, F>(input: ParseState<'a>, name: &'static str, f: F) -> IResult<ParseState<'a>, O>
+    where F: FnOnce(ParseState<'a>) -> IResult<ParseState<'a>, O>
+{
+    let failure = match input.failure {
+        Some(failure) => failure,
+        None => return f(input),
+    };
+    // If the parser fails without getting past its first token, report the
+    // parser itself as expected rather than each token it tried.
+    let start = skip_whitespace(input).idx();
+    let mark = failure.mark(start);
+    let result = f(input);
+    if let IResult::Error = result {
+        failure.relabel(start, mark, Expected::Named(name));
+    }
+    result
 }
 
 /// Turn a failed parse into `None` and a successful parse into `Some`.
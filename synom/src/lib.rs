@@ -23,6 +23,8 @@
 
 extern crate unicode_xid;
 
+use std::cell::RefCell;
+use std::fmt::{self, Display};
 use std::str::{CharIndices, Chars, Bytes};
 
 #[doc(hidden)]
@@ -38,6 +40,8 @@ pub mod helper;
 pub struct ParseState<'a> {
     input: &'a str,
     index: usize,
+    offset: usize,
+    failure: Option<&'a FurthestFailure>,
 }
 
 impl<'a> ParseState<'a> {
@@ -45,9 +49,55 @@ impl<'a> ParseState<'a> {
         ParseState {
             input: s,
             index: 0,
+            offset: 0,
+            failure: None,
         }
     }
 
+    /// Like `new`, but records the furthest position at which a parser failed
+    /// in `failure`.
+    pub fn tracking(s: &'a str, failure: &'a FurthestFailure) -> ParseState<'a> {
+        ParseState {
+            input: s,
+            index: 0,
+            offset: 0,
+            failure: Some(failure),
+        }
+    }
+
+    /// Treats the input as starting at `offset` within some larger source, so
+    /// that `idx()`, and the spans built from it, are relative to the start of
+    /// that source rather than of the input.
+    ///
+    /// ```rust
+    /// extern crate cpp_synom as synom;
+    /// use synom::ParseState;
+    ///
+    /// fn main() {
+    ///     let file = "fn f() { foo!(a + b) }";
+    ///     let state = ParseState::new(&file[14..19]).with_offset(14);
+    ///     assert_eq!(state.idx(), 14);
+    ///     assert_eq!(state.advance(4).idx(), 18);
+    /// }
+    /// ```
+    pub fn with_offset(self, offset: usize) -> ParseState<'a> {
+        ParseState { offset: offset, ..self }
+    }
+
+    /// Records that `expected` would have been accepted at the current
+    /// position, if this state is tracking failures.
+    pub fn expected(self, expected: Expected) {
+        if let Some(failure) = self.failure {
+            failure.record(self.idx(), expected);
+        }
+    }
+
+    /// The same position without failure tracking, for use in lookahead where
+    /// a failing parser is not an error.
+    pub fn untracked(self) -> ParseState<'a> {
+        ParseState { failure: None, ..self }
+    }
+
     pub fn rest(self) -> &'a str {
         &self.input[self.index..]
     }
@@ -83,21 +133,95 @@ impl<'a> ParseState<'a> {
     pub fn advance(self, i: usize) -> ParseState<'a> {
         let index = i + self.index;
         assert!(index <= self.input.len());
-        ParseState {
-            input: self.input,
-            index: index,
-        }
+        ParseState { index: index, ..self }
     }
 
     pub fn finish(self) -> ParseState<'a> {
-        ParseState {
-            input: self.input,
-            index: self.input.len(),
-        }
+        ParseState { index: self.input.len(), ..self }
     }
 
     pub fn idx(self) -> usize {
-        self.index
+        self.offset + self.index
+    }
+}
+
+/// Something a parser would have accepted at the position where it failed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Expected {
+    /// A piece of punctuation or a keyword, e.g. `;` or `fn`.
+    Token(&'static str),
+    /// A construct given a name with `label!`, e.g. `type`.
+    Named(&'static str),
+}
+
+impl Display for Expected {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Expected::Token(token) => write!(formatter, "`{}`", token),
+            Expected::Named(name) => formatter.write_str(name),
+        }
+    }
+}
+
+/// Tracks the furthest position at which any parser failed, and what would
+/// have been accepted there.
+///
+/// Pass it to `ParseState::tracking` before parsing. When the parse fails,
+/// the furthest failure is usually the best place to report the error.
+#[derive(Debug, Default)]
+pub struct FurthestFailure {
+    inner: RefCell<(Option<usize>, Vec<Expected>)>,
+}
+
+impl FurthestFailure {
+    pub fn new() -> Self {
+        FurthestFailure::default()
+    }
+
+    /// The furthest index into the input at which a parser failed.
+    pub fn position(&self) -> Option<usize> {
+        self.inner.borrow().0
+    }
+
+    /// Everything which would have been accepted at `position()`, sorted and
+    /// without duplicates.
+    pub fn expected(&self) -> Vec<Expected> {
+        let mut expected = self.inner.borrow().1.clone();
+        expected.sort();
+        expected.dedup();
+        expected
+    }
+
+    fn record(&self, index: usize, expected: Expected) {
+        let mut inner = self.inner.borrow_mut();
+        match inner.0 {
+            Some(furthest) if furthest > index => return,
+            Some(furthest) if furthest == index => {}
+            _ => {
+                inner.0 = Some(index);
+                inner.1.clear();
+            }
+        }
+        inner.1.push(expected);
+    }
+
+    /// The number of expectations recorded at `index`.
+    fn mark(&self, index: usize) -> usize {
+        let inner = self.inner.borrow();
+        if inner.0 == Some(index) {
+            inner.1.len()
+        } else {
+            0
+        }
+    }
+
+    /// Replaces everything recorded at `index` since `mark` with `expected`.
+    fn relabel(&self, index: usize, mark: usize, expected: Expected) {
+        let mut inner = self.inner.borrow_mut();
+        if inner.0 == Some(index) {
+            inner.1.truncate(mark);
+            inner.1.push(expected);
+        }
     }
 }
 
@@ -323,7 +447,7 @@ macro_rules! map {
 #[macro_export]
 macro_rules! not {
     ($i:expr, $submac:ident!( $($args:tt)* )) => {
-        match $submac!($i, $($args)*) {
+        match $submac!($i.untracked(), $($args)*) {
             $crate::IResult::Done(_, _) => $crate::IResult::Error,
             $crate::IResult::Error => $crate::IResult::Done($i, ()),
         }
@@ -0,0 +1,128 @@
+#![cfg(feature = "full")]
+
+extern crate cpp_syn as syn;
+use syn::*;
+
+#[macro_use]
+extern crate quote;
+
+fn body_expr(block: &Block) -> &Expr {
+    match block.stmts.last().unwrap().node {
+        StmtKind::Expr(ref expr) => expr,
+        ref node => panic!("expected expression statement, got {:?}", node),
+    }
+}
+
+#[test]
+fn test_async_fn() {
+    let raw = "pub async unsafe fn f(x: Fut) -> u8 { x.await }";
+
+    let item = parse_item(raw).unwrap();
+    match item.node {
+        ItemKind::Fn(_, unsafety, constness, asyncness, _, _, ref block) => {
+            assert_eq!(unsafety, Unsafety::Unsafe);
+            assert_eq!(constness, Constness::NotConst);
+            assert_eq!(asyncness, Asyncness::Async);
+            match body_expr(block).node {
+                ExprKind::Await(ref fut) => assert_eq!(fut.span, Span { lo: 38, hi: 39 }),
+                ref node => panic!("expected await, got {:?}", node),
+            }
+            assert_eq!(body_expr(block).span, Span { lo: 38, hi: 45 });
+        }
+        _ => panic!("expected fn item"),
+    }
+    assert_eq!(quote!(#item).to_string(),
+               "pub async unsafe fn f ( x : Fut ) -> u8 { x . await }");
+
+    match parse_item("fn f() {}").unwrap().node {
+        ItemKind::Fn(_, _, _, asyncness, _, _, _) => assert_eq!(asyncness, Asyncness::NotAsync),
+        _ => panic!("expected fn item"),
+    }
+}
+
+#[test]
+fn test_async_methods() {
+    let raw = "impl S { pub async fn f(&self) {} } trait T { async fn g(); }";
+
+    let items = parse_items(raw).unwrap();
+    match items[0].node {
+        ItemKind::Impl(_, _, _, _, _, ref items) => {
+            match items[0].node {
+                ImplItemKind::Method(ref sig, _) => assert_eq!(sig.asyncness, Asyncness::Async),
+                _ => panic!("expected method"),
+            }
+        }
+        _ => panic!("expected impl"),
+    }
+    match items[1].node {
+        ItemKind::Trait(_, _, _, ref items) => {
+            match items[0].node {
+                TraitItemKind::Method(ref sig, _) => assert_eq!(sig.asyncness, Asyncness::Async),
+                _ => panic!("expected method"),
+            }
+        }
+        _ => panic!("expected trait"),
+    }
+    assert_eq!(quote!(#(#items)*).to_string(),
+               "impl S { pub async fn f ( & self ) { } } trait T { async fn g ( ) ; }");
+}
+
+#[test]
+fn test_async_closure() {
+    let expr = parse_expr("async move |x| x.await").unwrap();
+    match expr.node {
+        ExprKind::Closure(capture, asyncness, _, ref body) => {
+            assert_eq!(capture, CaptureBy::Value);
+            assert_eq!(asyncness, Asyncness::Async);
+            match body.node {
+                ExprKind::Await(_) => {}
+                ref node => panic!("expected await, got {:?}", node),
+            }
+        }
+        ref node => panic!("expected closure, got {:?}", node),
+    }
+    assert_eq!(quote!(#expr).to_string(), "async move | x | x . await");
+}
+
+#[test]
+fn test_async_block() {
+    let expr = parse_expr("async move { f().await?.len() }").unwrap();
+    let block = match expr.node {
+        ExprKind::Async(CaptureBy::Value, ref block) => block,
+        ref node => panic!("expected async block, got {:?}", node),
+    };
+    match body_expr(block).node {
+        ExprKind::MethodCall(_, _, ref args) => {
+            match args[0].node {
+                ExprKind::Try(ref inner) => {
+                    match inner.node {
+                        ExprKind::Await(_) => {}
+                        ref node => panic!("expected await, got {:?}", node),
+                    }
+                }
+                ref node => panic!("expected try, got {:?}", node),
+            }
+        }
+        ref node => panic!("expected method call, got {:?}", node),
+    }
+    assert_eq!(quote!(#expr).to_string(), "async move { f ( ) . await ? . len ( ) }");
+
+    let expr = parse_expr("async { 1 }").unwrap();
+    match expr.node {
+        ExprKind::Async(CaptureBy::Ref, _) => {}
+        ref node => panic!("expected async block, got {:?}", node),
+    }
+}
+
+#[test]
+fn test_async_ident() {
+    // `async` and `await` are identifiers outside of these positions
+    match parse_expr("async").unwrap().node {
+        ExprKind::Path(None, ref path) => assert_eq!(path.segments[0].ident, "async"),
+        ref node => panic!("expected path, got {:?}", node),
+    }
+    match parse_expr("x.await()").unwrap().node {
+        ExprKind::MethodCall(ref method, _, _) => assert_eq!(method, "await"),
+        ref node => panic!("expected method call, got {:?}", node),
+    }
+}
@@ -0,0 +1,127 @@
+extern crate cpp_syn as syn;
+use syn::*;
+
+fn span(lo: usize, hi: usize) -> Span {
+    Span { lo: lo, hi: hi }
+}
+
+const SOURCE: &str = "fn main() {\n    let x: u8 = \"a\";\n    foo(1,\n        2);\n}\n";
+
+fn source_map() -> SourceMap {
+    let mut map = SourceMap::new();
+    map.add_file("src/main.rs", SOURCE);
+    map
+}
+
+#[test]
+fn test_single_line() {
+    let lit = SOURCE.find("\"a\"").unwrap();
+    let ty = SOURCE.find("u8").unwrap();
+    let diagnostic = Diagnostic::error("mismatched types", span(lit, lit + 3))
+        .label("expected `u8`")
+        .secondary(span(ty, ty + 2), "expected due to this")
+        .secondary(span(ty - 3, ty - 2), "binding")
+        .note("string literals have type `&str`");
+
+    let expected = concat!("error: mismatched types\n",
+                           " --> src/main.rs:2:17\n",
+                           "  |\n",
+                           "2 |     let x: u8 = \"a\";\n",
+                           "  |         -  --   ^^^ expected `u8`\n",
+                           "  |         |  |\n",
+                           "  |         |  expected due to this\n",
+                           "  |         binding\n",
+                           "  |\n",
+                           "  = note: string literals have type `&str`");
+    assert_eq!(diagnostic.render(&source_map()), expected);
+}
+
+#[test]
+fn test_multi_line() {
+    let call = SOURCE.find("foo").unwrap();
+    let diagnostic = Diagnostic::warning("unused result", span(call, call + 17))
+        .label("this call");
+
+    let expected = concat!("warning: unused result\n",
+                           " --> src/main.rs:3:5\n",
+                           "  |\n",
+                           "3 | /     foo(1,\n",
+                           "4 | |         2);\n",
+                           "  | |__________^ this call");
+    assert_eq!(diagnostic.render(&source_map()), expected);
+
+    let call = SOURCE.find("foo").unwrap();
+    let diagnostic = Diagnostic::error("bad block", span(10, SOURCE.len()))
+        .label("block")
+        .secondary(span(call, call + 18), "call")
+        .help("remove it");
+
+    let expected = concat!("error: bad block\n",
+                           " --> src/main.rs:1:11\n",
+                           "  |\n",
+                           "1 |    fn main() {\n",
+                           "  |  ____________^\n",
+                           "2 | |      let x: u8 = \"a\";\n",
+                           "3 | |/     foo(1,\n",
+                           "4 | ||         2);\n",
+                           "  | ||___________- call\n",
+                           "5 | |  }\n",
+                           "  | |__^ block\n",
+                           "  |\n",
+                           "  = help: remove it");
+    assert_eq!(diagnostic.render(&source_map()), expected);
+}
+
+#[test]
+fn test_elided_lines() {
+    let diagnostic = Diagnostic::error("whole file", span(0, SOURCE.len()));
+
+    let expected = concat!("error: whole file\n",
+                           " --> src/main.rs:1:1\n",
+                           "  |\n",
+                           "1 | / fn main() {\n",
+                           "2 | |     let x: u8 = \"a\";\n",
+                           "...\n",
+                           "4 | |         2);\n",
+                           "5 | | }\n",
+                           "  | |_^");
+    assert_eq!(diagnostic.render(&source_map()), expected);
+}
+
+#[test]
+fn test_ansi() {
+    let diagnostic = Diagnostic::error("oops", span(3, 7)).label("here");
+
+    let expected = concat!("\x1b[1;31merror\x1b[0m\x1b[1m: oops\x1b[0m\n",
+                           " \x1b[1;34m-->\x1b[0m src/main.rs:1:4\n",
+                           "  \x1b[1;34m|\x1b[0m\n",
+                           "\x1b[1;34m1\x1b[0m \x1b[1;34m|\x1b[0m fn main() {\n",
+                           "  \x1b[1;34m|\x1b[0m    \x1b[1;31m^^^^\x1b[0m \x1b[1;31mhere\x1b[0m");
+    assert_eq!(diagnostic.render_ansi(&source_map()), expected);
+}
+
+#[test]
+fn test_outside_file() {
+    let diagnostic = Diagnostic::error("no location", span(1000, 1000)).note("sorry");
+    assert_eq!(diagnostic.render(&source_map()), "error: no location\n= note: sorry");
+}
+
+#[test]
+fn test_errors() {
+    let mut errors = Errors::new();
+    assert!(errors.is_empty());
+    assert_eq!(errors.clone().finish(), Ok(()));
+
+    errors.error("unsupported \"union\"", span(0, 5));
+    errors.push(Diagnostic::error("missing field", span(6, 7)).note("add `x: u8`"));
+    errors.push(parse_derive_input("struct S { a: u8 b: u8 }").unwrap_err());
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors.diagnostics()[2].span(), span(17, 18));
+
+    let tokens = errors.finish().unwrap_err();
+    let expected = concat!("compile_error ! ( \"unsupported \\\"union\\\"\" ) ; ",
+                           "compile_error ! ( \"missing field\\n= note: add `x: u8`\" ) ; ",
+                           "compile_error ! ( \"failed to parse derive input, expected `!`, `(`, ",
+                           "`+`, `,`, `::`, `<` or `}`, found `b`\" ) ;");
+    assert_eq!(tokens.to_string(), expected);
+}
@@ -1,9 +1,18 @@
 extern crate cpp_syn as syn;
 use syn::*;
+use syn::fold::Folder;
 
 #[macro_use]
 extern crate quote;
 
+struct StripSpans;
+impl Folder for StripSpans {
+    fn fold_span(&mut self, span: Span) -> Span {
+        assert!(span != EMPTY_SPAN);
+        EMPTY_SPAN
+    }
+}
+
 #[test]
 fn test_split_for_impl() {
     // <'a, 'b: 'a, #[may_dangle] T: 'a = ()> where T: Debug
@@ -13,6 +22,7 @@ fn test_split_for_impl() {
                 attrs: Vec::new(),
                 lifetime: Lifetime::new("'a"),
                 bounds: Vec::new(),
+                span: EMPTY_SPAN,
             },
             LifetimeDef {
                 attrs: Vec::new(),
@@ -20,14 +30,18 @@ fn test_split_for_impl() {
                 bounds: vec![
                     Lifetime::new("'a"),
                 ],
+                span: EMPTY_SPAN,
             },
         ],
-        ty_params: vec![
-            TyParam {
+        params: vec![
+            GenericParam::Type(TyParam {
                 attrs: vec![
                     Attribute {
                         style: AttrStyle::Outer,
-                        value: MetaItem::Word("may_dangle".into()),
+                        value: MetaItem {
+                            node: MetaItemKind::Word("may_dangle".into()),
+                            span: EMPTY_SPAN,
+                        },
                         is_sugared_doc: false,
                         span: EMPTY_SPAN,
                     },
@@ -36,14 +50,15 @@ fn test_split_for_impl() {
                 bounds: vec![
                     TyParamBound::Region(Lifetime::new("'a")),
                 ],
-                default: Some(Ty::Tup(Vec::new())),
-            },
+                default: Some(Ty { node: TyKind::Tup(Vec::new()), span: EMPTY_SPAN }),
+                span: EMPTY_SPAN,
+            }),
         ],
         where_clause: WhereClause {
             predicates: vec![
                 WherePredicate::BoundPredicate(WhereBoundPredicate {
                     bound_lifetimes: Vec::new(),
-                    bounded_ty: Ty::Path(None, "T".into()),
+                    bounded_ty: Ty { node: TyKind::Path(None, "T".into()), span: EMPTY_SPAN },
                     bounds: vec![
                         TyParamBound::Trait(
                             PolyTraitRef {
@@ -53,9 +68,12 @@ fn test_split_for_impl() {
                             TraitBoundModifier::None,
                         ),
                     ],
+                    span: EMPTY_SPAN,
                 }),
             ],
+            where_span: Some(EMPTY_SPAN),
         },
+        span: EMPTY_SPAN,
     };
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
@@ -75,11 +93,74 @@ fn test_split_for_impl() {
     assert_eq!(expected, tokens.to_string());
 }
 
+#[test]
+fn test_const_params() {
+    let raw = "struct S<'a, T: Clone = u8, #[attr] const N: usize = 3, const M: i8 = { -1 }>;";
+
+    let input = parse_derive_input(raw).unwrap();
+    let generics = &input.generics;
+    assert_eq!(generics.params.len(), 3);
+    let param = match generics.params[1] {
+        GenericParam::Const(ref param) => param,
+        ref param => panic!("expected const parameter, got {:?}", param),
+    };
+    assert_eq!(param.ident, "N");
+    assert_eq!(StripSpans.fold_ty(param.ty.clone()), Ty::from(TyKind::Path(None, "usize".into())));
+    match param.default {
+        Some(ConstExpr::Lit(ref lit)) => assert_eq!(lit.span, Span { lo: 53, hi: 54 }),
+        ref default => panic!("expected literal default, got {:?}", default),
+    }
+    assert_eq!(param.span, Span { lo: 28, hi: 54 });
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let tokens = quote! {
+        impl #impl_generics MyTrait for S #ty_generics #where_clause {}
+    };
+    let expected = concat!("impl < 'a , T : Clone , # [ attr ] const N : usize , const M : i8 > ",
+                           "MyTrait for S < 'a , T , N , M > { }");
+    assert_eq!(expected, tokens.to_string());
+
+    let turbofish = ty_generics.as_turbofish();
+    assert_eq!("S :: < 'a , T , N , M >", quote!(S #turbofish).to_string());
+
+    assert_eq!(quote!(#input).to_string(),
+               "struct S < 'a , T : Clone = u8 , # [ attr ] const N : usize = 3 , \
+                const M : i8 = { - 1 } > ;");
+
+    // Const parameters on their own
+    let input = parse_derive_input("struct A<const N: usize = M>([u8; N]);").unwrap();
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    assert_eq!(quote!(impl #impl_generics A #ty_generics).to_string(),
+               "impl < const N : usize > A < N >");
+    match input.generics.params[0] {
+        GenericParam::Const(ConstParam { default: Some(ConstExpr::Path(ref path)), .. }) => {
+            assert_eq!(path.segments[0].ident, "M")
+        }
+        ref param => panic!("expected const parameter with path default, got {:?}", param),
+    }
+
+    // Const parameters before type parameters keep their position
+    let input = parse_derive_input("struct S<const N: usize, T>([T; N]);").unwrap();
+    match (&input.generics.params[0], &input.generics.params[1]) {
+        (&GenericParam::Const(ref n), &GenericParam::Type(ref t)) => {
+            assert_eq!(n.ident, "N");
+            assert_eq!(t.ident, "T");
+        }
+        params => panic!("unexpected parameters {:?}", params),
+    }
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    assert_eq!(quote!(impl #impl_generics S #ty_generics).to_string(),
+               "impl < const N : usize , T > S < N , T >");
+    assert_eq!(quote!(#input).to_string(),
+               "struct S < const N : usize , T > ( [ T ; N ] ) ;");
+}
+
 #[test]
 fn test_ty_param_bound() {
     let tokens = quote!('a);
     let expected = TyParamBound::Region(Lifetime::new("'a"));
-    assert_eq!(expected, parse_ty_param_bound(tokens.as_str()).unwrap());
+    let bound = parse_ty_param_bound(tokens.as_str()).unwrap();
+    assert_eq!(expected, StripSpans.fold_ty_param_bound(bound));
 
     let tokens = quote!(Debug);
     let expected = TyParamBound::Trait(
@@ -89,7 +170,8 @@ fn test_ty_param_bound() {
         },
         TraitBoundModifier::None,
     );
-    assert_eq!(expected, parse_ty_param_bound(tokens.as_str()).unwrap());
+    let bound = parse_ty_param_bound(tokens.as_str()).unwrap();
+    assert_eq!(expected, StripSpans.fold_ty_param_bound(bound));
 
     let tokens = quote!(?Sized);
     let expected = TyParamBound::Trait(
@@ -99,5 +181,50 @@ fn test_ty_param_bound() {
         },
         TraitBoundModifier::Maybe,
     );
-    assert_eq!(expected, parse_ty_param_bound(tokens.as_str()).unwrap());
+    let bound = parse_ty_param_bound(tokens.as_str()).unwrap();
+    assert_eq!(expected, StripSpans.fold_ty_param_bound(bound));
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_generic_associated_types() {
+    let raw = "trait T { type Item<'a>: Debug where Self: 'a; } \
+               impl T for S { type Item<'a> where Self: 'a = &'a u8; }";
+
+    let items = parse_items(raw).unwrap();
+    match items[0].node {
+        ItemKind::Trait(_, _, _, ref items) => {
+            match items[0].node {
+                TraitItemKind::Type(ref generics, ref bounds, None) => {
+                    assert_eq!(generics.lifetimes[0].lifetime.ident, "'a");
+                    assert_eq!(generics.span, Span { lo: 19, hi: 23 });
+                    assert_eq!(generics.where_clause.predicates.len(), 1);
+                    assert_eq!(bounds.len(), 1);
+                }
+                ref node => panic!("expected associated type, got {:?}", node),
+            }
+        }
+        _ => panic!("expected trait"),
+    }
+    match items[1].node {
+        ItemKind::Impl(_, _, _, _, _, ref items) => {
+            match items[0].node {
+                ImplItemKind::Type(ref generics, _) => {
+                    assert_eq!(generics.lifetimes.len(), 1);
+                    assert_eq!(generics.where_clause.predicates.len(), 1);
+                }
+                ref node => panic!("expected associated type, got {:?}", node),
+            }
+        }
+        _ => panic!("expected impl"),
+    }
+    // The where clause is printed after the type
+    assert_eq!(quote!(#(#items)*).to_string(),
+               "trait T { type Item < 'a > : Debug where Self : 'a ; } \
+                impl T for S { type Item < 'a > = & 'a u8 where Self : 'a ; }");
+
+    let raw = "impl T for S { type Item<'a> = &'a u8 where Self: 'a; type Plain = u8; }";
+    let item = parse_item(raw).unwrap();
+    assert_eq!(quote!(#item).to_string(),
+               "impl T for S { type Item < 'a > = & 'a u8 where Self : 'a ; type Plain = u8 ; }");
 }
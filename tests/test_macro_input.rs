@@ -16,7 +16,7 @@ fn test_unit() {
 
     let expected = MacroInput {
         ident: "Unit".into(),
-        vis: Visibility::Inherited,
+        vis: Visibility { node: VisibilityKind::Inherited, span: None },
         attrs: Vec::new(),
         generics: Generics::default(),
         body: Body::Struct(VariantData::Unit),
@@ -39,14 +39,29 @@ fn test_struct() {
 
     let expected = MacroInput {
         ident: "Item".into(),
-        vis: Visibility::Public,
+        vis: Visibility { node: VisibilityKind::Public, span: Some(EMPTY_SPAN) },
         attrs: vec![
             Attribute {
                 style: AttrStyle::Outer,
-                value: MetaItem::List("derive".into(), vec![
-                    NestedMetaItem::MetaItem(MetaItem::Word("Debug".into())),
-                    NestedMetaItem::MetaItem(MetaItem::Word("Clone".into())),
-                ]),
+                value: MetaItem {
+                    node: MetaItemKind::List("derive".into(), vec![
+                        NestedMetaItem {
+                            node: NestedMetaItemKind::MetaItem(MetaItem {
+                                node: MetaItemKind::Word("Debug".into()),
+                                span: EMPTY_SPAN,
+                            }),
+                            span: EMPTY_SPAN,
+                        },
+                        NestedMetaItem {
+                            node: NestedMetaItemKind::MetaItem(MetaItem {
+                                node: MetaItemKind::Word("Clone".into()),
+                                span: EMPTY_SPAN,
+                            }),
+                            span: EMPTY_SPAN,
+                        },
+                    ]),
+                    span: EMPTY_SPAN,
+                },
                 is_sugared_doc: false,
                 span: EMPTY_SPAN,
             },
@@ -55,29 +70,44 @@ fn test_struct() {
         body: Body::Struct(VariantData::Struct(vec![
             Field {
                 ident: Some("ident".into()),
-                vis: Visibility::Public,
+                vis: Visibility { node: VisibilityKind::Public, span: Some(EMPTY_SPAN) },
                 attrs: Vec::new(),
-                ty: Ty::Path(None, "Ident".into()),
+                ty: Ty { node: TyKind::Path(None, "Ident".into()), span: EMPTY_SPAN },
+                colon_span: Some(EMPTY_SPAN),
+                span: EMPTY_SPAN,
             },
             Field {
                 ident: Some("attrs".into()),
-                vis: Visibility::Public,
+                vis: Visibility { node: VisibilityKind::Public, span: Some(EMPTY_SPAN) },
                 attrs: Vec::new(),
-                ty: Ty::Path(None, Path {
-                    global: false,
-                    segments: vec![
-                        PathSegment {
-                            ident: "Vec".into(),
-                            parameters: PathParameters::AngleBracketed(
-                                AngleBracketedParameterData {
-                                    lifetimes: Vec::new(),
-                                    types: vec![Ty::Path(None, "Attribute".into())],
-                                    bindings: Vec::new(),
-                                },
-                            ),
-                        }
-                    ],
-                }),
+                ty: Ty {
+                    node: TyKind::Path(None, Path {
+                        global: false,
+                        segments: vec![
+                            PathSegment {
+                                ident: "Vec".into(),
+                                parameters: PathParameters::AngleBracketed(
+                                    AngleBracketedParameterData {
+                                        lifetimes: Vec::new(),
+                                        args: vec![
+                                            GenericArg::Type(Ty {
+                                                node: TyKind::Path(None, "Attribute".into()),
+                                                span: EMPTY_SPAN,
+                                            }),
+                                        ],
+                                        bindings: Vec::new(),
+                                        span: EMPTY_SPAN,
+                                    },
+                                ),
+                                span: EMPTY_SPAN,
+                            }
+                        ],
+                        span: EMPTY_SPAN,
+                    }),
+                    span: EMPTY_SPAN,
+                },
+                colon_span: Some(EMPTY_SPAN),
+                span: EMPTY_SPAN,
             },
         ])),
         span: EMPTY_SPAN,
@@ -101,47 +131,56 @@ fn test_enum() {
 
     let expected = MacroInput {
         ident: "Result".into(),
-        vis: Visibility::Public,
+        vis: Visibility { node: VisibilityKind::Public, span: Some(EMPTY_SPAN) },
         attrs: vec![
             Attribute {
                 style: AttrStyle::Outer,
-                value: MetaItem::NameValue(
-                    "doc".into(),
-                    Lit {
-                        node: LitKind::Str(
-                            "/// See the std::result module documentation for details.".into(),
-                            StrStyle::Cooked,
-                        ),
-                        span: EMPTY_SPAN,
-                    }
-                ),
+                value: MetaItem {
+                    node: MetaItemKind::NameValue(
+                        "doc".into(),
+                        Lit {
+                            node: LitKind::Str(
+                                "/// See the std::result module documentation for details.".into(),
+                                StrStyle::Cooked,
+                            ),
+                            span: EMPTY_SPAN,
+                        }
+                    ),
+                    span: EMPTY_SPAN,
+                },
                 is_sugared_doc: true,
                 span: EMPTY_SPAN,
             },
             Attribute {
                 style: AttrStyle::Outer,
-                value: MetaItem::Word("must_use".into()),
+                value: MetaItem {
+                    node: MetaItemKind::Word("must_use".into()),
+                    span: EMPTY_SPAN,
+                },
                 is_sugared_doc: false,
                 span: EMPTY_SPAN,
             },
         ],
         generics: Generics {
             lifetimes: Vec::new(),
-            ty_params: vec![
-                TyParam {
+            params: vec![
+                GenericParam::Type(TyParam {
                     attrs: Vec::new(),
                     ident: "T".into(),
                     bounds: Vec::new(),
                     default: None,
-                },
-                TyParam {
+                    span: EMPTY_SPAN,
+                }),
+                GenericParam::Type(TyParam {
                     attrs: Vec::new(),
                     ident: "E".into(),
                     bounds: Vec::new(),
                     default: None,
-                },
+                    span: EMPTY_SPAN,
+                }),
             ],
-            where_clause: WhereClause { predicates: Vec::new() },
+            where_clause: WhereClause { predicates: Vec::new(), where_span: None },
+            span: EMPTY_SPAN,
         },
         body: Body::Enum(vec![
             Variant {
@@ -150,12 +189,16 @@ fn test_enum() {
                 data: VariantData::Tuple(vec![
                     Field {
                         ident: None,
-                        vis: Visibility::Inherited,
+                        vis: Visibility { node: VisibilityKind::Inherited, span: None },
                         attrs: Vec::new(),
-                        ty: Ty::Path(None, "T".into()),
+                        ty: Ty { node: TyKind::Path(None, "T".into()), span: EMPTY_SPAN },
+                        colon_span: None,
+                        span: EMPTY_SPAN,
                     },
                 ]),
                 discriminant: None,
+                discriminant_span: None,
+                span: EMPTY_SPAN,
             },
             Variant {
                 ident: "Err".into(),
@@ -163,12 +206,16 @@ fn test_enum() {
                 data: VariantData::Tuple(vec![
                     Field {
                         ident: None,
-                        vis: Visibility::Inherited,
+                        vis: Visibility { node: VisibilityKind::Inherited, span: None },
                         attrs: Vec::new(),
-                        ty: Ty::Path(None, "E".into()),
+                        ty: Ty { node: TyKind::Path(None, "E".into()), span: EMPTY_SPAN },
+                        colon_span: None,
+                        span: EMPTY_SPAN,
                     },
                 ]),
                 discriminant: None,
+                discriminant_span: None,
+                span: EMPTY_SPAN,
             },
             Variant {
                 ident: "Surprise".into(),
@@ -178,6 +225,8 @@ fn test_enum() {
                     node: LitKind::Int(0, IntTy::Isize),
                     span: EMPTY_SPAN,
                 })),
+                discriminant_span: Some(EMPTY_SPAN),
+                span: EMPTY_SPAN,
             },
         ]),
         span: EMPTY_SPAN,
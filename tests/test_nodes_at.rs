@@ -0,0 +1,67 @@
+#![cfg(all(feature = "full", feature = "visit"))]
+
+extern crate cpp_syn as syn;
+use syn::*;
+
+fn kind(node: &Node) -> &'static str {
+    match *node {
+        Node::Item(_) => "item",
+        Node::ForeignItem(_) => "foreign item",
+        Node::TraitItem(_) => "trait item",
+        Node::ImplItem(_) => "impl item",
+        Node::Variant(_) => "variant",
+        Node::Field(_) => "field",
+        Node::Generics(_) => "generics",
+        Node::FnDecl(_) => "fn decl",
+        Node::Block(_) => "block",
+        Node::Stmt(_) => "stmt",
+        Node::Local(_) => "local",
+        Node::Expr(_) => "expr",
+        Node::Pat(_) => "pat",
+        Node::Ty(_) => "ty",
+        Node::Path(_) => "path",
+        Node::PathSegment(_) => "path segment",
+        Node::Mac(_) => "mac",
+        Node::Attribute(_) => "attribute",
+        Node::Lifetime(_) => "lifetime",
+        Node::Lit(_) => "lit",
+        Node::Ident(_) => "ident",
+    }
+}
+
+#[test]
+fn test_nodes_at() {
+    let raw = "struct S;\nfn f(x: u8) -> u8 { let y = foo::bar(x); y }";
+    let krate = parse_crate(raw).unwrap();
+
+    let offset = raw.find("bar").unwrap() + 1;
+    let kinds: Vec<_> = krate.nodes_at(offset).iter().map(kind).collect();
+    assert_eq!(kinds,
+               ["item", "block", "stmt", "local", "expr", "expr", "path", "path segment", "ident"]);
+
+    match krate.node_at(offset) {
+        Some(Node::Ident(ident)) => assert_eq!(ident, "bar"),
+        other => panic!("expected ident, found {:?}", other),
+    }
+
+    let offset = raw.find("u8").unwrap();
+    let kinds: Vec<_> = krate.nodes_at(offset).iter().map(kind).collect();
+    assert_eq!(kinds, ["item", "fn decl", "ty", "path", "path segment", "ident"]);
+
+    // Between items
+    assert!(krate.nodes_at(9).is_empty());
+    assert!(krate.node_at(raw.len()).is_none());
+}
+
+#[test]
+fn test_nodes_at_expr() {
+    let raw = "a + b * c";
+    let expr = parse_expr(raw).unwrap();
+
+    let kinds: Vec<_> = expr.nodes_at(4).iter().map(kind).collect();
+    assert_eq!(kinds, ["expr", "expr", "expr", "path", "path segment", "ident"]);
+
+    let item = parse_item("impl S { fn f() {} }").unwrap();
+    let kinds: Vec<_> = item.nodes_at(12).iter().map(kind).collect();
+    assert_eq!(kinds, ["item", "impl item", "ident"]);
+}
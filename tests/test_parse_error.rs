@@ -0,0 +1,157 @@
+extern crate cpp_syn as syn;
+use syn::*;
+
+#[macro_use]
+extern crate quote;
+
+fn span(lo: usize, hi: usize) -> Span {
+    Span { lo: lo, hi: hi }
+}
+
+#[test]
+fn test_trailing_tokens() {
+    let err = parse_type("u8 u16").unwrap_err();
+    assert_eq!(err.construct(), "type");
+    assert_eq!(err.span(), span(3, 6));
+    assert_eq!(err.line(), 1);
+    assert_eq!(err.column(), 3);
+
+    let expected = concat!("error: unparsed tokens after type, expected `!`, `(`, `+`, `::` or \
+                            `<`, found `u16`\n",
+                           " --> 1:4\n",
+                           "  |\n",
+                           "1 | u8 u16\n",
+                           "  |    ^^^");
+    assert_eq!(err.to_string(), expected);
+}
+
+#[test]
+fn test_failed_parse() {
+    let err = parse_derive_input("\n\n  #[derive]\n  enum {}").unwrap_err();
+    assert_eq!(err.construct(), "derive input");
+    assert_eq!(err.span(), span(21, 22));
+    assert_eq!(err.line(), 4);
+    assert_eq!(err.column(), 7);
+
+    let expected = concat!("error: failed to parse derive input, expected identifier, found `{`\n",
+                           " --> 4:8\n",
+                           "  |\n",
+                           "4 |   enum {}\n",
+                           "  |        ^");
+    assert_eq!(err.to_string(), expected);
+
+    let err = parse_ident("").unwrap_err();
+    assert_eq!(err.span(), span(0, 0));
+    assert_eq!(err.expected(), &[Expected::Named("identifier")]);
+    assert!(err.to_string().ends_with("1 | \n  | ^"));
+}
+
+#[test]
+fn test_expected() {
+    let input = "struct S { a: u8 b: u8 }";
+    let err = ParseError::new("struct", input, span(17, 22))
+        .with_expected(vec![Expected::Token(","), Expected::Token("}")]);
+    assert_eq!(err.expected(), &[Expected::Token(","), Expected::Token("}")]);
+
+    let expected = concat!("error: failed to parse struct, expected `,` or `}`, found `b: u8`\n",
+                           " --> 1:18\n",
+                           "  |\n",
+                           "1 | struct S { a: u8 b: u8 }\n",
+                           "  |                  ^^^^^");
+    assert_eq!(err.to_string(), expected);
+}
+
+#[test]
+fn test_span_clamped() {
+    let err = ParseError::new("type", "é", span(1, 5));
+    assert_eq!(err.span(), span(0, 2));
+    let err = ParseError::new("type", "u8", span(7, 3));
+    assert_eq!(err.span(), span(2, 2));
+    assert!(err.to_string().ends_with("1 | u8\n  |   ^"));
+}
+
+#[test]
+fn test_furthest_failure() {
+    let err = parse_derive_input("struct S { a: u8 b: u8 }").unwrap_err();
+    assert_eq!(err.span(), span(17, 18));
+    assert!(err.expected().contains(&Expected::Token(",")));
+    assert!(err.expected().contains(&Expected::Token("}")));
+    assert!(err.to_string().ends_with(", found `b`\n --> 1:18\n  |\n1 | struct S { a: u8 b: u8 }\n  |                  ^"));
+
+    let err = parse_type("Vec<u8").unwrap_err();
+    assert_eq!(err.span(), span(6, 6));
+    assert!(err.expected().contains(&Expected::Token(">")));
+    assert!(err.to_string().contains("found end of input"));
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_furthest_failure_label() {
+    // Types, expressions and patterns which fail on their first token are
+    // reported by name rather than by every token they tried.
+    let err = parse_item("type A = ;").unwrap_err();
+    assert_eq!(err.span(), span(9, 10));
+    assert_eq!(err.expected(), &[Expected::Named("type")]);
+
+    let err = parse_item("fn f() { let = 1; }").unwrap_err();
+    assert_eq!(err.expected(), &[Expected::Named("pattern")]);
+
+    let err = parse_item("fn f() { let x = ; }").unwrap_err();
+    assert_eq!(err.span(), span(17, 18));
+    assert!(err.to_string().starts_with("error: failed to parse item, expected expression, found `;`"));
+
+    // Other parsers report the tokens they tried.
+    let err = parse_item("struct S fn").unwrap_err();
+    assert_eq!(err.span(), span(9, 11));
+    assert_eq!(err.expected(),
+               &[Expected::Token("("),
+                 Expected::Token(";"),
+                 Expected::Token("<"),
+                 Expected::Token("where"),
+                 Expected::Token("{")]);
+
+    let err = parse_item("fn f() { let x = 1 fn }").unwrap_err();
+    assert_eq!(err.span(), span(19, 21));
+    assert!(err.expected().contains(&Expected::Token(";")));
+    assert!(err.to_string().contains("found `fn`"));
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_recovering() {
+    let raw = "use a::b;\nfn f() { let x = 1 fn }\nstruct S fn\nstruct T;\nimpl X {";
+
+    let (krate, errors) = parse_crate_recovering(raw);
+    let kinds: Vec<_> = krate.items
+        .iter()
+        .map(|item| match item.node {
+            ItemKind::Use(_) => "use",
+            ItemKind::Struct(..) => "struct",
+            ItemKind::Error(_) => "error",
+            _ => "other",
+        })
+        .collect();
+    assert_eq!(kinds, ["use", "error", "error", "error", "struct", "error"]);
+
+    assert_eq!(krate.items[1].span, span(10, 33));
+    assert_eq!(krate.items[2].span, span(34, 42));
+    assert_eq!(krate.items[3].span, span(43, 45));
+    assert_eq!(krate.items[5].span, span(56, 64));
+
+    assert_eq!(errors.len(), 4);
+    assert_eq!(errors[0].span(), span(29, 31));
+    assert_eq!(errors[0].line(), 2);
+    assert!(errors[0].expected().contains(&Expected::Token(";")));
+    assert_eq!(errors[1].span(), span(43, 45));
+    assert_eq!(errors[3].span(), span(64, 64));
+    assert!(errors[3].to_string().contains("found end of input"));
+
+    // Skipped source is printed verbatim
+    assert_eq!(krate.items[2].node, ItemKind::Error("struct S".to_owned()));
+    assert_eq!(quote!(#krate).to_string(),
+               "use a :: b ; fn f() { let x = 1 fn } struct S fn struct T ; impl X {");
+
+    let (krate, errors) = parse_crate_recovering("#![feature(x)]\nfn f() {}\n");
+    assert!(errors.is_empty());
+    assert_eq!(krate, parse_crate("#![feature(x)]\nfn f() {}\n").unwrap());
+}
@@ -0,0 +1,88 @@
+extern crate cpp_syn as syn;
+use syn::*;
+
+fn span(lo: usize, hi: usize) -> Span {
+    Span { lo: lo, hi: hi }
+}
+
+#[test]
+fn test_replace_node() {
+    let raw = "struct S {\n    a: u8, // a\n    b: Vec<u8>,\n}\n";
+
+    let input = parse_derive_input(raw).unwrap();
+    let fields = match input.body {
+        Body::Struct(VariantData::Struct(fields)) => fields,
+        _ => panic!("expected struct body"),
+    };
+
+    let mut rewriter = Rewriter::new(raw).unwrap();
+    rewriter.replace(fields[0].ty.span, &fields[0].ty);
+    assert!(rewriter.edits().is_empty());
+
+    // Only the text which differs from the source is replaced
+    rewriter.replace(fields[1].ty.span, &parse_type("Vec < u16 >").unwrap());
+    assert_eq!(rewriter.edits(),
+               &[TextEdit {
+                     span: span(39, 40),
+                     text: "16".to_owned(),
+                 }]);
+    assert_eq!(rewriter.apply(), "struct S {\n    a: u8, // a\n    b: Vec<u16>,\n}\n");
+}
+
+#[test]
+fn test_replace_text() {
+    let raw = "type A = (u8, u16);";
+
+    let mut rewriter = Rewriter::new(raw).unwrap();
+    rewriter.replace_text(span(14, 17), "u32");
+    rewriter.remove(span(12, 14));
+    rewriter.replace_text(span(10, 10), "i8, ");
+    rewriter.replace_text(span(10, 10), "i16, ");
+    rewriter.replace_text(span(19, 19), " // tuple");
+    assert_eq!(rewriter.source(), raw);
+    assert_eq!(rewriter.apply(), "type A = (i8, i16, u8u32); // tuple");
+
+    let edits = rewriter.into_edits();
+    let spans: Vec<_> = edits.iter().map(|edit| edit.span).collect();
+    assert_eq!(spans, [span(10, 10), span(10, 10), span(12, 14), span(14, 17), span(19, 19)]);
+}
+
+#[test]
+#[should_panic(expected = "overlaps the replaced span")]
+fn test_overlapping_edits() {
+    let mut rewriter = Rewriter::new("type A = (u8, u16);").unwrap();
+    rewriter.replace_text(span(9, 18), "()");
+    rewriter.replace_text(span(10, 10), "i8, ");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_rewrite_crate() {
+    let raw = "// header\n\nfn a(x: u8) -> u8 {\n    // double\n    match x { 0 => 0, _ => x * 2 }\n}\n\n/// Doc\nfn b() {}\n";
+
+    let (krate, trivia) = parse_crate_lossless(raw).unwrap();
+    let mut rewriter = Rewriter::from(trivia);
+
+    // Only the modified tokens of a replaced node are reformatted
+    let mut item = krate.items[0].clone();
+    item.ident = Ident::new("double");
+    rewriter.replace(item.span, &item);
+
+    let mut item = krate.items[1].clone();
+    item.vis = Visibility::from(VisibilityKind::Public);
+    rewriter.replace(item.span, &item);
+
+    let ident = krate.items[0].ident.span.unwrap();
+    let vis = raw.find("fn b").unwrap();
+    assert_eq!(rewriter.edits(),
+               &[TextEdit {
+                     span: ident,
+                     text: "double".to_owned(),
+                 },
+                 TextEdit {
+                     span: span(vis, vis),
+                     text: "pub ".to_owned(),
+                 }]);
+    assert_eq!(rewriter.apply(),
+               "// header\n\nfn double(x: u8) -> u8 {\n    // double\n    match x { 0 => 0, _ => x * 2 }\n}\n\n/// Doc\npub fn b() {}\n");
+}
@@ -0,0 +1,62 @@
+extern crate cpp_syn as syn;
+use syn::*;
+
+fn span(lo: usize, hi: usize) -> Span {
+    Span { lo: lo, hi: hi }
+}
+
+#[test]
+fn test_lookup() {
+    let raw = "struct S {\n    a: u8,\n    é: Vec<𝒜>,\r\n}\n";
+
+    let mut map = SourceMap::new();
+    let file = map.add_file("s.rs", raw);
+    assert_eq!(map.file(file).name(), "s.rs");
+    assert_eq!(map.file(file).span(), span(0, raw.len()));
+    assert_eq!(map.file(file).line_count(), 5);
+    assert_eq!(map.file(file).line(3), Some("    é: Vec<𝒜>,"));
+    assert_eq!(map.file(file).line(5), Some(""));
+    assert_eq!(map.file(file).line(6), None);
+
+    let input = parse_macro_input(raw).unwrap();
+    let fields = match input.body {
+        Body::Struct(VariantData::Struct(fields)) => fields,
+        _ => panic!("expected struct body"),
+    };
+
+    let loc = map.lookup(fields[0].span).unwrap();
+    assert_eq!(loc.file, file);
+    assert_eq!(loc.lo, Loc { line: 2, col: 4, col_char: 4, col_utf16: 4 });
+    assert_eq!(loc.hi, Loc { line: 2, col: 9, col_char: 9, col_utf16: 9 });
+    assert_eq!(map.span_text(fields[0].span), Some("a: u8"));
+
+    let ty = fields[1].ty.span;
+    let loc = map.lookup(ty).unwrap();
+    assert_eq!(loc.lo, Loc { line: 3, col: 8, col_char: 7, col_utf16: 7 });
+    assert_eq!(loc.hi, Loc { line: 3, col: 17, col_char: 13, col_utf16: 14 });
+    assert_eq!(map.span_text(ty), Some("Vec<𝒜>"));
+    // Spans which split a char have no text
+    assert_eq!(map.span_text(span(26, 28)), Some("é"));
+    assert_eq!(map.span_text(span(26, 27)), None);
+}
+
+#[test]
+fn test_multiple_files() {
+    let mut map = SourceMap::new();
+    let first = map.add_file("a.rs", "fn a() {}");
+    let second = map.add_file("b.rs", "fn b() {}\nfn c() {}");
+    assert!(first != second);
+    assert_eq!(map.files().len(), 2);
+    assert_eq!(map.file(second).span(), span(10, 29));
+
+    assert_eq!(map.lookup(span(9, 9)).unwrap().file, first);
+    assert_eq!(map.lookup(span(10, 12)).unwrap().file, second);
+    assert_eq!(map.span_text(span(23, 24)), Some("c"));
+
+    let loc = map.lookup(span(23, 24)).unwrap();
+    assert_eq!(loc.lo, Loc { line: 2, col: 3, col_char: 3, col_utf16: 3 });
+
+    // Spans which cross files or lie past the end are not resolved
+    assert_eq!(map.lookup(span(5, 12)), None);
+    assert_eq!(map.lookup(span(30, 30)), None);
+}
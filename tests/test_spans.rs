@@ -0,0 +1,496 @@
+extern crate cpp_syn as syn;
+extern crate cpp_synom as synom;
+use syn::*;
+
+fn span(lo: usize, hi: usize) -> Span {
+    Span { lo: lo, hi: hi }
+}
+
+#[test]
+fn test_ty_spans() {
+    let raw = " &'a Vec<(u8, fn(bool) -> i32)>";
+
+    let ty = parse_type(raw).unwrap();
+    assert_eq!(ty.span, span(1, 31));
+
+    let target = match ty.node {
+        TyKind::Rptr(_, target) => target.ty,
+        _ => panic!("expected reference type"),
+    };
+    assert_eq!(target.span, span(5, 31));
+
+    let tup = match target.node {
+        TyKind::Path(None, path) => {
+            match path.segments[0].parameters {
+                PathParameters::AngleBracketed(ref data) => {
+                    match data.args[0] {
+                        GenericArg::Type(ref ty) => ty.clone(),
+                        _ => panic!("expected type argument"),
+                    }
+                }
+                _ => panic!("expected angle bracketed parameters"),
+            }
+        }
+        _ => panic!("expected path type"),
+    };
+    assert_eq!(tup.span, span(9, 30));
+
+    let elems = match tup.node {
+        TyKind::Tup(elems) => elems,
+        _ => panic!("expected tuple type"),
+    };
+    assert_eq!(elems[0].span, span(10, 12));
+    assert_eq!(elems[1].span, span(14, 29));
+
+    let bare_fn = match elems[1].node {
+        TyKind::BareFn(ref bare_fn) => bare_fn.clone(),
+        _ => panic!("expected bare fn type"),
+    };
+    assert_eq!(bare_fn.inputs[0].ty.span, span(17, 21));
+    assert_eq!(bare_fn.rarrow_span, Some(span(23, 25)));
+    match bare_fn.output {
+        FunctionRetTy::Ty(ref ty) => assert_eq!(ty.span, span(26, 29)),
+        FunctionRetTy::Default => panic!("expected return type"),
+    }
+
+    match parse_type("fn(u8)").unwrap().node {
+        TyKind::BareFn(ref bare_fn) => assert_eq!(bare_fn.rarrow_span, None),
+        _ => panic!("expected bare fn type"),
+    }
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_pat_spans() {
+    let raw = "match x { Foo { a, box ref b } | [c, ..] => 1, }";
+
+    let expr = parse_expr(raw).unwrap();
+    let arm = match expr.node {
+        ExprKind::Match(_, ref arms) => arms[0].clone(),
+        _ => panic!("expected match expression"),
+    };
+    assert_eq!(arm.span, span(10, 45));
+    assert_eq!(arm.pats[0].span, span(10, 30));
+    assert_eq!(arm.pats[1].span, span(33, 40));
+
+    let fields = match arm.pats[0].node {
+        PatKind::Struct(_, ref fields, _) => fields.clone(),
+        _ => panic!("expected struct pattern"),
+    };
+    assert_eq!(fields[0].span, span(16, 17));
+    assert_eq!(fields[1].span, span(19, 28));
+    assert_eq!(fields[1].pat.span, span(19, 28));
+    match fields[1].pat.node {
+        PatKind::Box(ref inner) => assert_eq!(inner.span, span(23, 28)),
+        _ => panic!("expected box pattern"),
+    }
+
+    match arm.pats[1].node {
+        PatKind::Slice(ref before, Some(ref rest), _) => {
+            assert_eq!(before[0].span, span(34, 35));
+            assert_eq!(rest.span, span(37, 39));
+        }
+        _ => panic!("expected slice pattern"),
+    }
+}
+
+#[test]
+fn test_path_spans() {
+    let raw = "::std::vec::Vec<Foo>";
+
+    let path = parse_path(raw).unwrap();
+    assert_eq!(path.span, span(0, 20));
+    assert_eq!(path.segments[0].span, span(2, 5));
+    assert_eq!(path.segments[2].span, span(12, 20));
+    match path.segments[2].parameters {
+        PathParameters::AngleBracketed(ref data) => {
+            assert_eq!(data.span, span(15, 20));
+            assert_eq!(data.args[0].span(), Some(span(16, 19)));
+        }
+        _ => panic!("expected angle bracketed parameters"),
+    }
+    match path.segments[0].parameters {
+        PathParameters::AngleBracketed(ref data) => assert_eq!(data.span, span(5, 5)),
+        _ => panic!("expected angle bracketed parameters"),
+    }
+
+    let raw = "<Vec<Foo> as a::Trait>::Item";
+
+    let ty = parse_type(raw).unwrap();
+    match ty.node {
+        TyKind::Path(Some(ref qself), ref path) => {
+            assert_eq!(qself.span, span(0, 22));
+            assert_eq!(qself.ty.span, span(1, 9));
+            assert_eq!(path.span, span(0, 28));
+            assert_eq!(path.segments[1].span, span(16, 21));
+            assert_eq!(path.segments[2].span, span(24, 28));
+        }
+        _ => panic!("expected qualified path type"),
+    }
+
+    let raw = "Fn(u8) -> bool";
+
+    let ty = parse_type(raw).unwrap();
+    match ty.node {
+        TyKind::Path(None, ref path) => {
+            assert_eq!(path.span, span(0, 14));
+            assert_eq!(path.segments[0].span, span(0, 14));
+        }
+        _ => panic!("expected path type"),
+    }
+
+    // The path of a shorthand field is the field name
+    #[cfg(feature = "full")]
+    {
+        let raw = "S { x }";
+
+        let expr = parse_expr(raw).unwrap();
+        match expr.node {
+            ExprKind::Struct(_, ref fields, _) => {
+                match fields[0].expr.node {
+                    ExprKind::Path(None, ref path) => {
+                        assert_eq!(path.span, span(4, 5));
+                        assert_eq!(path.segments[0].span, span(4, 5));
+                    }
+                    _ => panic!("expected path expression"),
+                }
+            }
+            _ => panic!("expected struct expression"),
+        }
+    }
+}
+
+#[test]
+fn test_ident_spans() {
+    let raw = "struct Foo<'a> { bar: &'a u8 }";
+
+    let input = parse_macro_input(raw).unwrap();
+    assert_eq!(input.ident, "Foo");
+    assert_eq!(input.ident, Ident::new("Foo"));
+    assert_eq!(input.ident.span, Some(span(7, 10)));
+    assert_eq!(input.generics.lifetimes[0].lifetime.ident.span, Some(span(11, 13)));
+
+    let fields = match input.body {
+        Body::Struct(VariantData::Struct(fields)) => fields,
+        _ => panic!("expected struct body"),
+    };
+    assert_eq!(fields[0].ident.as_ref().unwrap().span, Some(span(17, 20)));
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_stmt_spans() {
+    let raw = "fn f() { let x: u8 = 1; foo! { } bar(); x }";
+
+    let item = parse_item(raw).unwrap();
+    let block = match item.node {
+        ItemKind::Fn(_, _, _, _, _, _, block) => block,
+        _ => panic!("expected fn item"),
+    };
+    assert_eq!(block.span, span(7, 43));
+    assert_eq!(block.stmts[0].span, span(9, 23));
+    assert_eq!(block.stmts[1].span, span(24, 32));
+    assert_eq!(block.stmts[2].span, span(33, 39));
+    assert_eq!(block.stmts[3].span, span(40, 41));
+
+    match block.stmts[0].node {
+        StmtKind::Local(ref local) => {
+            assert_eq!(local.span, span(9, 23));
+            assert_eq!(local.pat.span, span(13, 14));
+        }
+        _ => panic!("expected local statement"),
+    }
+
+    let raw = "unsafe { if x {} }";
+
+    let expr = parse_expr(raw).unwrap();
+    match expr.node {
+        ExprKind::Block(_, ref block) => {
+            assert_eq!(block.span, span(7, 18));
+            match block.stmts[0].node {
+                StmtKind::Expr(ref expr) => {
+                    match expr.node {
+                        ExprKind::If(_, ref then_block, _) => {
+                            assert_eq!(then_block.span, span(14, 16));
+                        }
+                        _ => panic!("expected if expression"),
+                    }
+                }
+                _ => panic!("expected expression statement"),
+            }
+        }
+        _ => panic!("expected block expression"),
+    }
+}
+
+#[test]
+fn test_generics_spans() {
+    let raw = "struct S<'a: 'b, #[may_dangle] T: 'a = ()> where T: Clone, 'a: 'static { t: T }";
+
+    let input = parse_macro_input(raw).unwrap();
+    let generics = input.generics;
+    assert_eq!(generics.span, span(8, 42));
+    assert_eq!(generics.lifetimes[0].span, span(9, 15));
+    assert_eq!(generics.lifetimes[0].lifetime.ident.span, Some(span(9, 11)));
+    assert_eq!(generics.lifetimes[0].bounds[0].ident.span, Some(span(13, 15)));
+    // Lifetimes compare by name only, like identifiers.
+    assert_eq!(generics.lifetimes[0].lifetime, Lifetime::new("'a"));
+    assert_eq!(generics.params[0].span(), Some(span(17, 41)));
+
+    match generics.where_clause.predicates[0] {
+        WherePredicate::BoundPredicate(ref predicate) => {
+            assert_eq!(predicate.span, span(49, 57));
+        }
+        _ => panic!("expected bound predicate"),
+    }
+    match generics.where_clause.predicates[1] {
+        WherePredicate::RegionPredicate(ref predicate) => {
+            assert_eq!(predicate.span, span(59, 70));
+        }
+        _ => panic!("expected region predicate"),
+    }
+
+    let input = parse_macro_input("struct Unit;").unwrap();
+    assert_eq!(input.generics.span, span(11, 11));
+}
+
+#[test]
+fn test_data_spans() {
+    let raw = "pub enum E { #[a] A(pub(crate) u8), B = 0x10 }";
+
+    let input = parse_macro_input(raw).unwrap();
+    assert_eq!(input.vis.span, Some(span(0, 3)));
+
+    let variants = match input.body {
+        Body::Enum(variants) => variants,
+        _ => panic!("expected enum body"),
+    };
+    assert_eq!(variants[0].span, span(13, 34));
+    assert_eq!(variants[0].discriminant_span, None);
+    assert_eq!(variants[1].span, span(36, 44));
+    assert_eq!(variants[1].discriminant_span, Some(span(40, 44)));
+
+    let field = &variants[0].data.fields()[0];
+    assert_eq!(field.span, span(20, 33));
+    assert_eq!(field.vis.span, Some(span(20, 30)));
+
+    let raw = "struct S { #[a] x: u8, pub y: u8 }";
+
+    let input = parse_macro_input(raw).unwrap();
+    assert_eq!(input.vis.span, None);
+
+    let fields = match input.body {
+        Body::Struct(VariantData::Struct(fields)) => fields,
+        _ => panic!("expected struct body"),
+    };
+    assert_eq!(fields[0].span, span(11, 21));
+    assert_eq!(fields[0].vis.node, VisibilityKind::Inherited);
+    assert_eq!(fields[0].vis.span, None);
+    assert_eq!(fields[1].span, span(23, 32));
+    assert_eq!(fields[1].vis.span, Some(span(23, 26)));
+}
+
+#[test]
+fn test_meta_item_spans() {
+    let raw = "#[mything(rename = \"x\", skip)] struct S;";
+
+    let input = parse_macro_input(raw).unwrap();
+    let meta_item = &input.attrs[0].value;
+    assert_eq!(meta_item.span, span(2, 29));
+
+    let nested = match meta_item.node {
+        MetaItemKind::List(ref name, ref nested) => {
+            assert_eq!(name.span, Some(span(2, 9)));
+            nested.clone()
+        }
+        _ => panic!("expected list meta item"),
+    };
+    assert_eq!(nested[0].span, span(10, 22));
+    assert_eq!(nested[1].span, span(24, 28));
+    match nested[0].node {
+        NestedMetaItemKind::MetaItem(ref meta_item) => {
+            assert_eq!(meta_item.span, span(10, 22));
+            match meta_item.node {
+                MetaItemKind::NameValue(ref key, ref value) => {
+                    assert_eq!(key.span, Some(span(10, 16)));
+                    assert_eq!(value.span, span(19, 22));
+                }
+                _ => panic!("expected name-value meta item"),
+            }
+        }
+        _ => panic!("expected nested meta item"),
+    }
+
+    let raw = "/// Docs\nstruct S;";
+
+    let input = parse_macro_input(raw).unwrap();
+    assert_eq!(input.attrs[0].value.span, span(0, 8));
+}
+
+#[test]
+fn test_offset_spans() {
+    let file = "type T = foo!(Vec<u8>);";
+
+    let state = synom::ParseState::new(&file[14..21]).with_offset(14);
+    let ty = syn::parse::ty(state).expect("type");
+    assert_eq!(ty.span, span(14, 21));
+    match ty.node {
+        TyKind::Path(None, ref path) => {
+            assert_eq!(path.segments[0].ident.span, Some(span(14, 17)));
+            match path.segments[0].parameters {
+                PathParameters::AngleBracketed(ref data) => {
+                    assert_eq!(data.args[0].span(), Some(span(18, 20)));
+                }
+                _ => panic!("expected angle bracketed parameters"),
+            }
+        }
+        _ => panic!("expected path type"),
+    }
+}
+
+#[cfg(feature = "fold")]
+#[test]
+fn test_shift_spans() {
+    use syn::fold::{Folder, ShiftSpans};
+
+    let file = "type T = foo!(Vec<u8>);";
+
+    let ty = parse_type(&file[14..21]).unwrap();
+    let shifted = ShiftSpans::new(14).fold_ty(ty.clone());
+    let state = synom::ParseState::new(&file[14..21]).with_offset(14);
+    assert_eq!(shifted, syn::parse::ty(state).expect("type"));
+    assert_eq!(shifted.span, span(14, 21));
+
+    // Synthetic spans are left alone only when asked
+    let ty = ShiftSpans::new(14).fold_ty(Ty::from(TyKind::Never));
+    assert_eq!(ty.span, span(14, 14));
+    let ty = ShiftSpans::skipping_synthetic(14).fold_ty(Ty::from(TyKind::Never));
+    assert_eq!(ty.span, EMPTY_SPAN);
+}
+
+#[cfg(all(feature = "fold", feature = "full"))]
+#[test]
+fn test_shift_spans_at_fragment_start() {
+    use syn::fold::{Folder, ShiftSpans};
+
+    let file = "mod m { fn f() { |x| x; } }";
+
+    // The fragment starts with an item without generics, and the closure's
+    // omitted argument type has an empty span. Shifting must agree with
+    // parsing at the offset for both.
+    let fragment = &file[8..25];
+    let item = parse_item(fragment).unwrap();
+    let shifted = ShiftSpans::new(8).fold_item(item);
+    let state = synom::ParseState::new(fragment).with_offset(8);
+    assert_eq!(shifted, syn::parse::item(state).expect("item"));
+    assert_eq!(shifted.span, span(8, 25));
+
+    // An empty span at offset 0 moves along with its siblings
+    let generics = Generics { span: span(0, 0), ..Generics::default() };
+    assert_eq!(ShiftSpans::new(8).fold_generics(generics).span, span(8, 8));
+}
+
+#[test]
+fn test_has_span() {
+    let raw = "struct S<T> where T: Clone, T: Copy { a: Vec<T>, b: (u8, T) }";
+
+    let input = parse_derive_input(raw).unwrap();
+    assert_eq!(input.span(), Some(span(0, 61)));
+    assert_eq!(input.generics.where_clause.span(), Some(span(12, 35)));
+    assert_eq!(input.body.span(), Some(span(38, 59)));
+
+    let fields = match input.body {
+        Body::Struct(VariantData::Struct(fields)) => fields,
+        _ => panic!("expected struct body"),
+    };
+    assert_eq!(fields[0].ty.span(), Some(span(41, 47)));
+    match fields[1].ty.node {
+        TyKind::Tup(ref elems) => assert_eq!(elems.span(), Some(span(53, 58))),
+        _ => panic!("expected tuple type"),
+    }
+
+    // Synthetic nodes have no span
+    assert_eq!(Ty::from(TyKind::Never).span(), None);
+    assert_eq!(Ident::new("x").span(), None);
+    assert_eq!(Mutability::Mutable.span(), None);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_has_span_full() {
+    let raw = "fn f(x: u8) -> u8 { foo!(x); }";
+
+    let item = parse_item(raw).unwrap();
+    assert_eq!(item.span(), Some(span(0, 30)));
+    match item.node {
+        ItemKind::Fn(ref decl, _, _, _, _, _, ref block) => {
+            assert_eq!(decl.span(), Some(span(5, 17)));
+            let mac = match block.stmts[0].node {
+                StmtKind::Item(ref item) => match item.node {
+                    ItemKind::Mac(ref mac) => mac,
+                    _ => panic!("expected macro item"),
+                },
+                _ => panic!("expected item statement"),
+            };
+            assert_eq!(mac.span(), Some(span(20, 27)));
+            assert_eq!(mac.tts[0].span(), span(24, 27));
+        }
+        _ => panic!("expected fn item"),
+    }
+}
+
+#[test]
+fn test_token_spans() {
+    let raw = "struct S<T> where T: Clone { a: T, b: (T, u8) }";
+
+    let input = parse_derive_input(raw).unwrap();
+    assert_eq!(input.generics.where_clause.where_span, Some(span(12, 17)));
+
+    let fields = match input.body {
+        Body::Struct(VariantData::Struct(fields)) => fields,
+        _ => panic!("expected struct body"),
+    };
+    assert_eq!(fields[0].colon_span, Some(span(30, 31)));
+    assert_eq!(fields[1].colon_span, Some(span(36, 37)));
+
+    let input = parse_derive_input("struct S(u8);").unwrap();
+    assert_eq!(input.generics.where_clause.where_span, None);
+    match input.body {
+        Body::Struct(VariantData::Tuple(fields)) => assert_eq!(fields[0].colon_span, None),
+        _ => panic!("expected tuple struct body"),
+    }
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_token_spans_full() {
+    let raw = "impl S { default fn f(&self) -> u8 { match 0 { _ if true => 1, _ => |x| -> u8 { x } } } }";
+
+    let item = parse_item(raw).unwrap();
+    let impl_item = match item.node {
+        ItemKind::Impl(_, _, _, _, _, ref items) => &items[0],
+        _ => panic!("expected impl"),
+    };
+    assert_eq!(impl_item.defaultness_span, Some(span(9, 16)));
+
+    let (decl, block) = match impl_item.node {
+        ImplItemKind::Method(ref sig, ref block) => (&sig.decl, block),
+        _ => panic!("expected method"),
+    };
+    assert_eq!(decl.rarrow_span, Some(span(29, 31)));
+
+    let arms = match block.stmts[0].node {
+        StmtKind::Expr(ref expr) => match expr.node {
+            ExprKind::Match(_, ref arms) => arms,
+            _ => panic!("expected match"),
+        },
+        _ => panic!("expected expression statement"),
+    };
+    assert_eq!(arms[0].fat_arrow_span, span(57, 59));
+    assert_eq!(arms[1].fat_arrow_span, span(65, 67));
+
+    match arms[1].body.node {
+        ExprKind::Closure(_, _, ref decl, _) => assert_eq!(decl.rarrow_span, Some(span(72, 74))),
+        _ => panic!("expected closure"),
+    }
+}
@@ -0,0 +1,119 @@
+extern crate cpp_syn as syn;
+use syn::*;
+
+#[test]
+fn test_derive_input_round_trip() {
+    let raw = "/// Doc\n#[derive(Debug)]\npub struct S<T: Into<Vec<u8>>> {\n    a: T, // first\n    /* b */ b: [u8; 0x10],\n}\n";
+
+    let trivia = Trivia::new(raw).unwrap();
+    let input = parse_derive_input(raw).unwrap();
+    assert_eq!(trivia.print(&input), raw);
+
+    // Only the trivia of unmodified tokens is kept
+    let mut input = input;
+    input.ident = Ident::new("Renamed");
+    assert_eq!(trivia.print(&input),
+               "/// Doc\n#[derive(Debug)]\npub struct Renamed<T: Into<Vec<u8>>> {\n    a: T, // first\n    /* b */ b: [u8; 0x10],\n}\n");
+}
+
+#[test]
+fn test_leading_trailing() {
+    let raw = "struct S { // open\n    a: u8, /* a */\n}";
+
+    let trivia = Trivia::new(raw).unwrap();
+    assert_eq!(trivia.source(), raw);
+    let input = parse_derive_input(raw).unwrap();
+    let field = match input.body {
+        Body::Struct(VariantData::Struct(ref fields)) => &fields[0],
+        _ => panic!("expected struct body"),
+    };
+    assert_eq!(trivia.leading(field.span), Some(" // open\n    "));
+    assert_eq!(trivia.trailing(field.span), Some(""));
+    assert_eq!(trivia.trailing(field.ty.span), Some(""));
+    assert_eq!(trivia.leading(input.span), Some(""));
+    assert_eq!(trivia.trailing(input.span), Some(""));
+
+    // Spans which don't start or end at a token
+    assert_eq!(trivia.leading(Span { lo: 1, hi: 6 }), None);
+    assert_eq!(trivia.trailing(Span { lo: 0, hi: 5 }), None);
+}
+
+#[test]
+fn test_trivia_error() {
+    let err = Trivia::new("struct S { a: u8 ").unwrap_err();
+    assert_eq!(err.span(), Span { lo: 17, hi: 17 });
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_crate_round_trip() {
+    let raw = "\u{feff}#!/usr/bin/env run-cargo-script\n\
+               //! Crate docs\n\
+               \n\
+               // A comment\n\
+               fn a(x: u8,) -> Vec<Vec<u8>> {\n    \
+                   x >> 1 + 0x10 /* add */\n\
+               }\n\
+               \n\
+               /// Doc\n\
+               struct S; // end";
+
+    let (krate, trivia) = parse_crate_lossless(raw).unwrap();
+    assert_eq!(trivia.print(&krate), raw);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_crate_edits() {
+    let raw = "// header\n\nfn a(x: u8,) -> u8 {\n    x + 0x10 // add\n}\n\n/// Doc\nstruct S; // end\n";
+
+    let (mut krate, trivia) = parse_crate_lossless(raw).unwrap();
+
+    krate.items[0].ident = Ident::new("b");
+    assert_eq!(trivia.print(&krate),
+               "// header\n\nfn b(x: u8,) -> u8 {\n    x + 0x10 // add\n}\n\n/// Doc\nstruct S; // end\n");
+
+    // A node printed on its own doesn't take the trivia around it
+    assert_eq!(trivia.print(&krate.items[1]), "/// Doc\nstruct S;");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_crate_insert() {
+    let raw = "fn a() {}\n\n/// Doc\nstruct S; // end\n";
+
+    let (mut krate, trivia) = parse_crate_lossless(raw).unwrap();
+
+    // New tokens are spaced by `ToTokens`. An inserted item takes the
+    // whitespace before the item after it, so it starts on a line of its own
+    // rather than after the closing brace of the item before it.
+    krate.items.insert(1, parse_item("fn c() {}").unwrap());
+    assert_eq!(trivia.print(&krate),
+               "fn a() {}\n\nfn c ( ) { }\n\n/// Doc\nstruct S; // end\n");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_crate_dropped_commas() {
+    // The parser drops commas which are optional, wherever they are
+    let raw = "fn f<T>(x: T)\nwhere\n    T: Clone,\n{\n    let g = |x,| x;\n}\n";
+
+    let (krate, trivia) = parse_crate_lossless(raw).unwrap();
+    assert_eq!(trivia.print(&krate), raw);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_crate_large_edit() {
+    // Too many changed tokens to diff, so only the unchanged tokens at the
+    // start and end keep their trivia
+    let elems = |value: &str| vec![value; 1000].join(", ");
+    let raw = format!("// header\nconst A: [u8; 1000] = [{}];\n\nstruct S; // end\n",
+                      elems("1"));
+
+    let (mut krate, trivia) = parse_crate_lossless(&raw).unwrap();
+    krate.items[0] = parse_item(&format!("const A: [u8; 1000] = [{}];", elems("2"))).unwrap();
+    let printed = trivia.print(&krate);
+    assert!(printed.starts_with("// header\nconst A: [u8; 1000] = [2"));
+    assert!(printed.ends_with("2];\n\nstruct S; // end\n"));
+}
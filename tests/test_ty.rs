@@ -0,0 +1,200 @@
+extern crate cpp_syn as syn;
+use syn::*;
+
+#[macro_use]
+extern crate quote;
+
+fn trait_bound(name: &str) -> TyParamBound {
+    TyParamBound::Trait(PolyTraitRef {
+                            bound_lifetimes: Vec::new(),
+                            trait_ref: name.into(),
+                        },
+                        TraitBoundModifier::None)
+}
+
+fn strip_bound(bound: &TyParamBound) -> TyParamBound {
+    match *bound {
+        TyParamBound::Trait(ref poly, modifier) => {
+            let name = poly.trait_ref.segments[0].ident.clone();
+            TyParamBound::Trait(PolyTraitRef {
+                                    bound_lifetimes: Vec::new(),
+                                    trait_ref: name.as_ref().into(),
+                                },
+                                modifier)
+        }
+        TyParamBound::Region(ref lifetime) => {
+            TyParamBound::Region(Lifetime::new(lifetime.ident.as_ref()))
+        }
+    }
+}
+
+fn trait_object(ty: &Ty) -> (Vec<TyParamBound>, TraitObjectSyntax) {
+    match ty.node {
+        TyKind::TraitObject(ref bounds, syntax) => {
+            (bounds.iter().map(strip_bound).collect(), syntax)
+        }
+        _ => panic!("expected trait object, got {:?}", ty),
+    }
+}
+
+fn first_ty_param(ty: &Ty) -> &Ty {
+    match ty.node {
+        TyKind::Path(None, ref path) => {
+            match path.segments[0].parameters {
+                PathParameters::AngleBracketed(ref data) => {
+                    match data.args[0] {
+                        GenericArg::Type(ref ty) => ty,
+                        ref arg => panic!("expected type argument, got {:?}", arg),
+                    }
+                }
+                _ => panic!("expected angle bracketed parameters"),
+            }
+        }
+        TyKind::Rptr(_, ref target) => &target.ty,
+        _ => panic!("expected path or reference type"),
+    }
+}
+
+#[test]
+fn test_dyn_trait() {
+    let ty = parse_type("Box<dyn Error + Send + 'static>").unwrap();
+    let (bounds, syntax) = trait_object(first_ty_param(&ty));
+    assert_eq!(syntax, TraitObjectSyntax::Dyn);
+    assert_eq!(bounds,
+               vec![trait_bound("Error"),
+                    trait_bound("Send"),
+                    TyParamBound::Region(Lifetime::new("'static"))]);
+    assert_eq!(quote!(#ty).to_string(), "Box < dyn Error + Send + 'static >");
+
+    let ty = parse_type("&dyn Any").unwrap();
+    let (bounds, syntax) = trait_object(first_ty_param(&ty));
+    assert_eq!(syntax, TraitObjectSyntax::Dyn);
+    assert_eq!(bounds, vec![trait_bound("Any")]);
+    assert_eq!(quote!(#ty).to_string(), "& dyn Any");
+}
+
+#[test]
+fn test_bare_trait_object() {
+    let ty = parse_type("Box<Error + Send>").unwrap();
+    let (bounds, syntax) = trait_object(first_ty_param(&ty));
+    assert_eq!(syntax, TraitObjectSyntax::None);
+    assert_eq!(bounds, vec![trait_bound("Error"), trait_bound("Send")]);
+    assert_eq!(quote!(#ty).to_string(), "Box < Error + Send >");
+}
+
+#[test]
+fn test_dyn_path() {
+    // `dyn` is only a keyword when followed by a trait
+    for raw in &["dyn", "dyn::Trait", "dyn<T>"] {
+        match parse_type(raw).unwrap().node {
+            TyKind::Path(None, ref path) => assert_eq!(path.segments[0].ident, "dyn"),
+            ref node => panic!("expected path type, got {:?}", node),
+        }
+    }
+}
+
+fn angle_bracketed(ty: &Ty) -> &AngleBracketedParameterData {
+    match ty.node {
+        TyKind::Path(None, ref path) => {
+            match path.segments.last().unwrap().parameters {
+                PathParameters::AngleBracketed(ref data) => data,
+                _ => panic!("expected angle bracketed parameters"),
+            }
+        }
+        _ => panic!("expected path type, got {:?}", ty),
+    }
+}
+
+#[test]
+fn test_const_args() {
+    let ty = parse_type("Foo<'a, T, 3, -1, { N + 1 }, Item = u8>").unwrap();
+    let data = angle_bracketed(&ty);
+    assert_eq!(data.lifetimes.len(), 1);
+    assert_eq!(data.args.len(), 4);
+    assert_eq!(data.bindings.len(), 1);
+    match (&data.args[0], &data.args[1], &data.args[2], &data.args[3]) {
+        (&GenericArg::Type(_),
+         &GenericArg::Const(ConstExpr::Lit(_)),
+         &GenericArg::Const(ConstExpr::Unary(UnOp::Neg, _)),
+         &GenericArg::Const(ConstExpr::Block(ref expr))) => {
+            match **expr {
+                ConstExpr::Binary(BinOp::Add, _, _) => {}
+                ref expr => panic!("expected addition, got {:?}", expr),
+            }
+        }
+        args => panic!("unexpected arguments {:?}", args),
+    }
+    assert_eq!(quote!(#ty).to_string(),
+               "Foo < 'a , T , 3 , - 1 , { N + 1 } , Item = u8 >");
+
+    // A plain path is parsed as a type
+    let ty = parse_type("[T; N]").unwrap();
+    match ty.node {
+        TyKind::Array(_, ConstExpr::Path(_)) => {}
+        ref node => panic!("expected array type, got {:?}", node),
+    }
+    let ty = parse_type("Foo<N>").unwrap();
+    let data = angle_bracketed(&ty);
+    assert_eq!(data.args.len(), 1);
+    match data.args[0] {
+        GenericArg::Type(_) => {}
+        ref arg => panic!("expected type argument, got {:?}", arg),
+    }
+
+    let ty = parse_type("Foo<b'x'>").unwrap();
+    let data = angle_bracketed(&ty);
+    assert_eq!(data.args.len(), 1);
+    match data.args[0] {
+        GenericArg::Const(ConstExpr::Lit(_)) => {}
+        ref arg => panic!("expected literal argument, got {:?}", arg),
+    }
+    assert_eq!(quote!(#ty).to_string(), "Foo < b'x' >");
+
+    let ty = parse_type("Foo<3>").unwrap();
+    let data = angle_bracketed(&ty);
+    assert_eq!(data.args.len(), 1);
+    match data.args[0] {
+        GenericArg::Const(_) => {}
+        ref arg => panic!("expected const argument, got {:?}", arg),
+    }
+    assert_eq!(quote!(#ty).to_string(), "Foo < 3 >");
+
+    // Const arguments before type arguments keep their position
+    let ty = parse_type("Foo<3, u8>").unwrap();
+    let data = angle_bracketed(&ty);
+    assert_eq!(data.args.len(), 2);
+    match (&data.args[0], &data.args[1]) {
+        (&GenericArg::Const(_), &GenericArg::Type(_)) => {}
+        args => panic!("unexpected arguments {:?}", args),
+    }
+    assert_eq!(quote!(#ty).to_string(), "Foo < 3 , u8 >");
+
+    // A char literal is not a lifetime
+    let ty = parse_type("Foo<'a'>").unwrap();
+    let data = angle_bracketed(&ty);
+    assert!(data.lifetimes.is_empty());
+    assert_eq!(data.args.len(), 1);
+    match data.args[0] {
+        GenericArg::Const(ConstExpr::Lit(Lit { node: LitKind::Char('a'), .. })) => {}
+        ref arg => panic!("expected char argument, got {:?}", arg),
+    }
+    assert_eq!(quote!(#ty).to_string(), "Foo < 'a' >");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_const_args_item() {
+    let item = parse_item("type A = Foo<3, u8>;").unwrap();
+    assert_eq!(quote!(#item).to_string(), "type A = Foo < 3 , u8 > ;");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_dyn_trait_crate() {
+    let raw = "fn f(x: &mut dyn Write) -> Result<(), Box<dyn Error + Send>> { Ok(()) }";
+
+    let krate = parse_crate(raw).unwrap();
+    assert_eq!(quote!(#krate).to_string(),
+               "fn f ( x : & mut dyn Write ) -> Result < ( ) , Box < dyn Error + Send > > { \
+                Ok ( ( ) ) }");
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "full")]
+
+extern crate cpp_syn as syn;
+use syn::*;
+
+#[macro_use]
+extern crate quote;
+
+fn view_path(raw: &str) -> ViewPath {
+    match parse_item(raw).unwrap().node {
+        ItemKind::Use(view_path) => *view_path,
+        node => panic!("expected use item, got {:?}", node),
+    }
+}
+
+fn path_str(path: &Path) -> String {
+    quote!(#path).to_string()
+}
+
+#[test]
+fn test_nested_groups() {
+    let raw = "use std::{io::{self, Read as R}, fmt, collections::*};";
+
+    let tree = view_path(raw);
+    let items = match tree {
+        ViewPath::List(ref path, ref items) => {
+            assert_eq!(path_str(path), "std");
+            items
+        }
+        ref tree => panic!("expected group, got {:?}", tree),
+    };
+    assert_eq!(items.len(), 3);
+    match items[0] {
+        ViewPath::List(ref path, ref nested) => {
+            assert_eq!(path_str(path), "io");
+            match nested[0] {
+                ViewPath::Simple(ref path, None) => assert_eq!(path_str(path), "self"),
+                ref tree => panic!("expected self, got {:?}", tree),
+            }
+            match nested[1] {
+                ViewPath::Simple(ref path, Some(ref rename)) => {
+                    assert_eq!(path_str(path), "Read");
+                    assert_eq!(rename, "R");
+                }
+                ref tree => panic!("expected rename, got {:?}", tree),
+            }
+        }
+        ref tree => panic!("expected nested group, got {:?}", tree),
+    }
+    match items[2] {
+        ViewPath::Glob(ref path) => assert_eq!(path_str(path), "collections"),
+        ref tree => panic!("expected glob, got {:?}", tree),
+    }
+
+    assert_eq!(quote!(use #tree;).to_string(),
+               "use std :: { io :: { self , Read as R } , fmt , collections :: * } ;");
+}
+
+#[test]
+fn test_leading_colons() {
+    let tree = view_path("use ::{a::{b, *}, c::d};");
+    match tree {
+        ViewPath::List(ref path, _) => {
+            assert!(path.global);
+            assert!(path.segments.is_empty());
+        }
+        ref tree => panic!("expected group, got {:?}", tree),
+    }
+    assert_eq!(quote!(#tree).to_string(), ":: { a :: { b , * } , c :: d }");
+
+    // Only the outermost path may start with `::`
+    assert!(parse_item("use ::{a::{b, *}, ::c::d};").is_err());
+    assert!(parse_item("use a::{::b};").is_err());
+    assert!(parse_item("use {::*};").is_err());
+
+    let tree = view_path("pub use ::std::{fmt::{Debug, Display},};");
+    assert_eq!(quote!(#tree).to_string(), ":: std :: { fmt :: { Debug , Display } }");
+
+    let tree = view_path("use {a, b::{}};");
+    assert_eq!(quote!(#tree).to_string(), "{ a , b :: { } }");
+}